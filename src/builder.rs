@@ -0,0 +1,240 @@
+use std::sync::Arc;
+use chrono::{Duration, NaiveTime};
+use rubalosim::sensor::{Sensor, SensorType};
+use crate::open_plan::DeskGrid;
+use crate::room::{Building, BuildingError, Coordinates, DoorPeriod, DoorSensorKind, DoorState, RoomProperties, RoomType, RulePolicy};
+
+/// Creates a [Building] step by step and numbers all of its sensors.
+/// <br/>Unlike the methods of [Building], the methods of the builder do not take the offspring number of the
+/// last created sensor, the builder keeps track of it, so that every sensor gets an unique number.
+/// [BuildingBuilder::build] returns the building together with the offspring number of the last created sensor,
+/// like [Building::from_specification], which is needed by
+/// [Parameters::set_number_of_sensors](rubalosim::simulator::parameters::Parameters::set_number_of_sensors).
+pub struct BuildingBuilder {
+    building: Building,
+    // offspring number of the last created sensor, -1 if no sensor was created yet
+    offspring_number: i64,
+}
+
+impl BuildingBuilder {
+    /// Creates a new builder for a building with only a ground floor.
+    pub fn new() -> Self {
+        return BuildingBuilder {
+            building: Building::new(),
+            offspring_number: -1,
+        }
+    }
+
+    /// Gets the building as it is created so far, e.g. to look up rooms.
+    pub fn get_building(&self) -> &Building {
+        return &self.building;
+    }
+
+    /// Gets the number of sensors created so far.
+    pub fn get_number_of_sensors(&self) -> i64 {
+        return self.offspring_number + 1;
+    }
+
+    /// Gets the offspring number of the last created sensor, -1 if no sensor was created yet.
+    pub fn get_offspring_number(&self) -> i64 {
+        return self.offspring_number;
+    }
+
+    /// Adds a new floor, see [Building::add_floor].
+    pub fn add_floor(&mut self, level: i32) -> Result<&mut Self, BuildingError> {
+        self.building.add_floor(level)?;
+        return Ok(self);
+    }
+
+    /// Sets the floor to which new rooms are added, see [Building::set_current_floor].
+    pub fn set_current_floor(&mut self, level: i32) -> Result<&mut Self, BuildingError> {
+        self.building.set_current_floor(level)?;
+        return Ok(self);
+    }
+
+    /// Adds a staircase to the current floor, see [Building::add_staircase].
+    pub fn add_staircase(&mut self, id_count: i32) -> Result<&mut Self, BuildingError> {
+        self.building.add_staircase(id_count)?;
+        return Ok(self);
+    }
+
+    /// Adds an elevator to the current floor, see [Building::add_elevator].
+    pub fn add_elevator(&mut self, id_count: i32) -> Result<&mut Self, BuildingError> {
+        self.building.add_elevator(id_count)?;
+        return Ok(self);
+    }
+
+    /// Adds a room with doors and its sensors to the current floor, see [Building::add_room_with_doors].
+    pub fn add_room_with_doors(&mut self, id_count: i32, what_sensor_should_be_create: Vec<(u32, SensorType)>, windows: bool) -> Result<&mut Self, BuildingError> {
+        self.offspring_number = self.building.add_room_with_doors(id_count, what_sensor_should_be_create, windows, self.offspring_number)?;
+        return Ok(self);
+    }
+
+    /// Adds a room without doors and its sensors to the current floor, see [Building::add_room_without_doors].
+    pub fn add_room_without_doors(&mut self, id_count: i32, what_sensor_should_be_create: Vec<(u32, SensorType)>, windows: bool) -> Result<&mut Self, BuildingError> {
+        self.offspring_number = self.building.add_room_without_doors(id_count, what_sensor_should_be_create, windows, self.offspring_number)?;
+        return Ok(self);
+    }
+
+    /// Adds sub rooms with doors and their sensors to a room, see [Building::add_sub_rooms_with_doors].
+    pub fn add_sub_rooms_with_doors(&mut self, number_of_rooms: i32, parent_id: String, what_sensor_should_be_create: Vec<(u32, SensorType)>, windows: bool) -> Result<&mut Self, BuildingError> {
+        self.offspring_number = self.building.add_sub_rooms_with_doors(number_of_rooms, parent_id, what_sensor_should_be_create, windows, self.offspring_number)?;
+        return Ok(self);
+    }
+
+    /// Adds sub rooms without doors and their sensors to a room, see [Building::add_sub_rooms_without_doors].
+    pub fn add_sub_rooms_without_doors(&mut self, number_of_rooms: i32, parent_id: String, what_sensor_should_be_create: Vec<(u32, SensorType)>, windows: bool) -> Result<&mut Self, BuildingError> {
+        self.offspring_number = self.building.add_sub_rooms_without_doors(number_of_rooms, parent_id, what_sensor_should_be_create, windows, self.offspring_number)?;
+        return Ok(self);
+    }
+
+    /// Adds an open-plan area with a grid of desks and their sensors to a room, see [Building::add_desk_grid].
+    pub fn add_desk_grid(&mut self, parent_id: String, grid: &DeskGrid) -> Result<&mut Self, BuildingError> {
+        self.offspring_number = self.building.add_desk_grid(parent_id, grid, self.offspring_number)?;
+        return Ok(self);
+    }
+
+    /// Creates the specific number of sensors of the specific types and adds them to an existing room,
+    /// see [Building::add_sensors_to_room].
+    pub fn add_sensors_to_room(&mut self, node_id: String, what_sensor_should_be_create: Vec<(u32, SensorType)>) -> Result<&mut Self, BuildingError> {
+        self.building.get_floor_of_room(node_id.as_str()).ok_or(BuildingError::UnknownRoomId(node_id.clone()))?;
+        let mut sensors = Vec::<Sensor>::new();
+        let mut offspring_number = self.offspring_number;
+        for pair in &what_sensor_should_be_create {
+            for _ in 0..pair.0 {
+                offspring_number += 1;
+                // the id is set by the building
+                sensors.push(Sensor::new(String::new(), pair.1.clone(), offspring_number));
+            }
+        }
+        self.building.add_sensors_to_room(node_id, sensors)?;
+        self.offspring_number = offspring_number;
+        return Ok(self);
+    }
+
+    /// Creates the specific number of sensors of the specific types and adds them to the door between two rooms,
+    /// see [Building::add_sensors_to_door].
+    pub fn add_sensors_to_door(&mut self, id_room_1: &str, id_room_2: &str, kind: DoorSensorKind, what_sensor_should_be_create: Vec<(u32, SensorType)>) -> Result<&mut Self, BuildingError> {
+        let mut sensors = Vec::<Sensor>::new();
+        let mut offspring_number = self.offspring_number;
+        for pair in &what_sensor_should_be_create {
+            for _ in 0..pair.0 {
+                offspring_number += 1;
+                // the id is set by the building
+                sensors.push(Sensor::new(String::new(), pair.1.clone(), offspring_number));
+            }
+        }
+        self.building.add_sensors_to_door(id_room_1, id_room_2, kind, sensors)?;
+        self.offspring_number = offspring_number;
+        return Ok(self);
+    }
+
+    /// Connects two rooms with a door, see [Building::new_door_connection].
+    pub fn new_door_connection(&mut self, id_room_1: String, id_room_2: String, door_id: i32) -> Result<&mut Self, BuildingError> {
+        self.building.new_door_connection(id_room_1, id_room_2, door_id)?;
+        return Ok(self);
+    }
+
+    /// Connects two rooms without a door, see [Building::new_no_door_connection].
+    pub fn new_no_door_connection(&mut self, id_room_1: String, id_room_2: String, door_id: i32) -> Result<&mut Self, BuildingError> {
+        self.building.new_no_door_connection(id_room_1, id_room_2, door_id)?;
+        return Ok(self);
+    }
+
+    /// Connects the staircases of two floors, see [Building::connect_staircases].
+    pub fn connect_staircases(&mut self, id_count: i32, level_1: i32, level_2: i32, travel_time: Duration) -> Result<&mut Self, BuildingError> {
+        self.building.connect_staircases(id_count, level_1, level_2, travel_time)?;
+        return Ok(self);
+    }
+
+    /// Connects the elevators of two floors, see [Building::connect_elevators].
+    pub fn connect_elevators(&mut self, id_count: i32, level_1: i32, level_2: i32, travel_time: Duration) -> Result<&mut Self, BuildingError> {
+        self.building.connect_elevators(id_count, level_1, level_2, travel_time)?;
+        return Ok(self);
+    }
+
+    /// Sets the physical properties of a room, see [Building::set_room_properties].
+    pub fn set_room_properties(&mut self, id: &str, properties: RoomProperties) -> Result<&mut Self, BuildingError> {
+        self.building.set_room_properties(id, properties)?;
+        return Ok(self);
+    }
+
+    /// Sets the type of a room, see [Building::set_room_type].
+    pub fn set_room_type(&mut self, id: &str, room_type: RoomType) -> Result<&mut Self, BuildingError> {
+        self.building.set_room_type(id, room_type)?;
+        return Ok(self);
+    }
+
+    /// Sets the policy for the rules of the lights of a room, see [Building::set_rule_policy].
+    pub fn set_rule_policy(&mut self, id: &str, rule_policy: RulePolicy) -> Result<&mut Self, BuildingError> {
+        self.building.set_rule_policy(id, rule_policy)?;
+        return Ok(self);
+    }
+
+    /// Sets the policy for the rules of the lights of all rooms of a type, see [Building::set_rule_policy_of_room_type].
+    pub fn set_rule_policy_of_room_type(&mut self, room_type: RoomType, rule_policy: RulePolicy) -> &mut Self {
+        self.building.set_rule_policy_of_room_type(room_type, rule_policy);
+        return self;
+    }
+
+    /// Sets the coordinates of a room, see [Building::set_coordinates].
+    pub fn set_coordinates(&mut self, id: &str, coordinates: Coordinates) -> Result<&mut Self, BuildingError> {
+        self.building.set_coordinates(id, coordinates)?;
+        return Ok(self);
+    }
+
+    /// Sets the length of the connection between two rooms, see [Building::set_connection_length].
+    pub fn set_connection_length(&mut self, id_room_1: &str, id_room_2: &str, length: f64) -> Result<&mut Self, BuildingError> {
+        self.building.set_connection_length(id_room_1, id_room_2, length)?;
+        return Ok(self);
+    }
+
+    /// Sets the state of the door between two rooms, see [Building::set_door_state].
+    pub fn set_door_state(&mut self, id_room_1: &str, id_room_2: &str, state: DoorState) -> Result<&mut Self, BuildingError> {
+        self.building.set_door_state(id_room_1, id_room_2, state)?;
+        return Ok(self);
+    }
+
+    /// Adds a period of the day to the door between two rooms, see [Building::add_door_period].
+    pub fn add_door_period(&mut self, id_room_1: &str, id_room_2: &str, period: DoorPeriod) -> Result<&mut Self, BuildingError> {
+        self.building.add_door_period(id_room_1, id_room_2, period)?;
+        return Ok(self);
+    }
+
+    /// Locks the doors of all sub rooms within a period of the day, see [Building::lock_sub_rooms].
+    pub fn lock_sub_rooms(&mut self, start: NaiveTime, end: NaiveTime) -> &mut Self {
+        self.building.lock_sub_rooms(start, end);
+        return self;
+    }
+
+    /// Sets the rooms where the movable objects enter the building, see [Building::set_entrances].
+    pub fn set_entrances(&mut self, ids: Vec<String>) -> Result<&mut Self, BuildingError> {
+        self.building.set_entrances(ids)?;
+        return Ok(self);
+    }
+
+    /// Sets the rooms where the movable objects leave the building, see [Building::set_exits].
+    pub fn set_exits(&mut self, ids: Vec<String>) -> Result<&mut Self, BuildingError> {
+        self.building.set_exits(ids)?;
+        return Ok(self);
+    }
+
+    /// Sets the rooms the movable objects move to and their attractiveness, see [Building::set_destinations].
+    pub fn set_destinations(&mut self, destinations: Vec<(String, u32)>) -> Result<&mut Self, BuildingError> {
+        self.building.set_destinations(destinations)?;
+        return Ok(self);
+    }
+
+    /// Finishes the building.
+    /// <br/>Returns the building and the offspring number of the last created sensor.
+    pub fn build(self) -> (Arc<Building>, i64) {
+        let offspring_number = self.offspring_number;
+        return (Arc::new(self.building), offspring_number);
+    }
+}
+
+impl Default for BuildingBuilder {
+    fn default() -> Self {
+        return BuildingBuilder::new();
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+/// Errors that can occur while reading a holiday file.
+#[derive(Debug)]
+pub enum CalendarError {
+    Io(std::io::Error),
+    /// A line of the holiday file, with its number starting at 1, does not start with a date of the format _YYYY-MM-DD_.
+    InvalidLine(usize, String),
+}
+
+impl Display for CalendarError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            CalendarError::Io(error) => write!(f, "could not read holidays: {}", error),
+            CalendarError::InvalidLine(number, line) => write!(f, "invalid holiday in line {}: {}", number, line),
+        };
+    }
+}
+
+impl std::error::Error for CalendarError {}
+
+impl From<std::io::Error> for CalendarError {
+    fn from(error: std::io::Error) -> Self {
+        return CalendarError::Io(error);
+    }
+}
+
+/// Kind of a day of a [Calendar].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DayKind {
+    Weekday,
+    Weekend,
+    Holiday,
+}
+
+/// Calendar of a simulation of several days, from the start to the end date, both included.
+/// <br/>Every day has an occupancy factor, the probability that a movable object comes to the building on that day,
+/// see [apply_population_on_day](crate::movement::apply_population_on_day). By default everyone comes on Monday to Friday
+/// and nobody on weekends and holidays.
+#[derive(Debug, Clone)]
+pub struct Calendar {
+    start: NaiveDate,
+    end: NaiveDate,
+    // occupancy factor of every day of the week, starting with monday
+    weekday_factors: [f64; 7],
+    holiday_factor: f64,
+    // names of the holidays by their date
+    holidays: BTreeMap<NaiveDate, String>,
+}
+
+impl Calendar {
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Self {
+        return Calendar {
+            start,
+            end,
+            weekday_factors: [1.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0],
+            holiday_factor: 0.0,
+            holidays: BTreeMap::new(),
+        }
+    }
+
+    pub fn get_start(&self) -> NaiveDate {
+        return self.start;
+    }
+
+    pub fn get_end(&self) -> NaiveDate {
+        return self.end;
+    }
+
+    /// Gets all days from the start to the end date, both included.
+    /// <br/>The vector is empty if the end is before the start.
+    pub fn get_days(&self) -> Vec<NaiveDate> {
+        let mut days = Vec::<NaiveDate>::new();
+        let mut day = self.start;
+        while day <= self.end {
+            days.push(day);
+            day = day + Duration::days(1);
+        }
+        return days;
+    }
+
+    /// Sets the occupancy factor of a specific day of the week, between 0 and 1. Infinite factors and NaN are replaced by 0.
+    pub fn set_occupancy_factor(&mut self, weekday: Weekday, factor: f64) {
+        self.weekday_factors[weekday.num_days_from_monday() as usize] = limit_factor(factor);
+    }
+
+    /// Sets the occupancy factor of Monday to Friday, between 0 and 1.
+    pub fn set_weekday_factor(&mut self, factor: f64) {
+        for weekday in [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri] {
+            self.set_occupancy_factor(weekday, factor);
+        }
+    }
+
+    /// Sets the occupancy factor of Saturday and Sunday, between 0 and 1.
+    pub fn set_weekend_factor(&mut self, factor: f64) {
+        self.set_occupancy_factor(Weekday::Sat, factor);
+        self.set_occupancy_factor(Weekday::Sun, factor);
+    }
+
+    /// Sets the occupancy factor of holidays, between 0 and 1, which replaces the one of their day of the week.
+    /// Infinite factors and NaN are replaced by 0.
+    pub fn set_holiday_factor(&mut self, factor: f64) {
+        self.holiday_factor = limit_factor(factor);
+    }
+
+    pub fn add_holiday(&mut self, date: NaiveDate, name: String) {
+        self.holidays.insert(date, name);
+    }
+
+    /// Adds the holidays of a file. Every line contains a date of the format _YYYY-MM-DD_, optionally followed by
+    /// a comma and the name of the holiday, e.g. _2024-12-25, Christmas Day_. Empty lines and lines starting with _#_ are ignored.
+    /// <br/>Returns an error if the file could not be read or if a line does not start with a date. In case of an error no holiday is added.
+    pub fn read_holidays(&mut self, path: String) -> Result<(), CalendarError> {
+        let text = fs::read_to_string(path)?;
+        let mut holidays = Vec::<(NaiveDate, String)>::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() | line.starts_with('#') {
+                continue
+            }
+            let (date, name) = line.split_once(',').unwrap_or((line, ""));
+            let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                .map_err(|_| CalendarError::InvalidLine(number + 1, line.to_string()))?;
+            holidays.push((date, name.trim().to_string()));
+        }
+        self.holidays.extend(holidays);
+        return Ok(());
+    }
+
+    /// Gets the name of the holiday on a specific date.
+    /// <br/>Returns None if the date is no holiday.
+    pub fn get_holiday(&self, date: NaiveDate) -> Option<&String> {
+        return self.holidays.get(&date);
+    }
+
+    pub fn get_day_kind(&self, date: NaiveDate) -> DayKind {
+        if self.holidays.contains_key(&date) {
+            return DayKind::Holiday;
+        }
+        return match date.weekday() {
+            Weekday::Sat | Weekday::Sun => DayKind::Weekend,
+            _ => DayKind::Weekday,
+        };
+    }
+
+    /// Gets the number of days of a specific kind in a year, e.g. to extrapolate the results of the simulated days to the year.
+    /// <br/>Only the holidays of the calendar are counted as holidays.
+    pub fn get_number_of_days(&self, year: i32, day_kind: DayKind) -> u32 {
+        let mut number_of_days = 0;
+        let mut day = match NaiveDate::from_ymd_opt(year, 1, 1) {
+            Some(day) => day,
+            None => return 0
+        };
+        while day.year() == year {
+            if self.get_day_kind(day) == day_kind {
+                number_of_days += 1;
+            }
+            day = match day.succ_opt() {
+                Some(day) => day,
+                None => break
+            };
+        }
+        return number_of_days;
+    }
+
+    /// Gets the occupancy factor of a specific date, which is the one of holidays or else the one of its day of the week.
+    pub fn get_occupancy_factor(&self, date: NaiveDate) -> f64 {
+        if self.holidays.contains_key(&date) {
+            return self.holiday_factor;
+        }
+        return self.weekday_factors[date.weekday().num_days_from_monday() as usize];
+    }
+}
+
+/// Limits an occupancy factor to the range from 0 to 1.
+fn limit_factor(factor: f64) -> f64 {
+    // clamp keeps NaN, which would let everyone come
+    if !factor.is_finite() {
+        return 0.0;
+    }
+    return factor.clamp(0.0, 1.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use super::*;
+
+    /// Writes a holiday file into the temporary directory and returns its path.
+    fn write_holidays(name: &str, text: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("building_automation_{}_{}.txt", std::process::id(), name));
+        fs::write(&path, text).unwrap();
+        return path;
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        return NaiveDate::from_ymd_opt(year, month, day).unwrap();
+    }
+
+    #[test]
+    fn holidays_are_read_from_a_file() {
+        let path = write_holidays("valid", "# holidays of 2024\n2024-12-25, Christmas Day\n\n2024-12-26\n");
+        let mut calendar = Calendar::new(date(2024, 12, 23), date(2024, 12, 29));
+        calendar.read_holidays(path.to_string_lossy().to_string()).unwrap();
+        fs::remove_file(path).unwrap();
+        assert_eq!(calendar.get_holiday(date(2024, 12, 25)), Some(&"Christmas Day".to_string()));
+        assert_eq!(calendar.get_holiday(date(2024, 12, 26)), Some(&String::new()));
+        assert_eq!(calendar.get_holiday(date(2024, 12, 24)), None);
+    }
+
+    #[test]
+    fn malformed_holidays_are_rejected_with_their_line() {
+        let path = write_holidays("malformed", "2024-12-25, Christmas Day\n\n25.12.2024, Christmas Day\n");
+        let mut calendar = Calendar::new(date(2024, 12, 23), date(2024, 12, 29));
+        let result = calendar.read_holidays(path.to_string_lossy().to_string());
+        fs::remove_file(path).unwrap();
+        assert!(matches!(result, Err(CalendarError::InvalidLine(3, line)) if line == "25.12.2024, Christmas Day"));
+        assert_eq!(calendar.get_holiday(date(2024, 12, 25)), None);
+    }
+
+    #[test]
+    fn holidays_override_the_factor_of_their_weekday() {
+        let mut calendar = Calendar::new(date(2024, 12, 23), date(2024, 12, 29));
+        calendar.set_weekday_factor(0.9);
+        calendar.set_holiday_factor(0.1);
+        calendar.add_holiday(date(2024, 12, 25), "Christmas Day".to_string());
+        assert_eq!(calendar.get_day_kind(date(2024, 12, 25)), DayKind::Holiday);
+        assert_eq!(calendar.get_occupancy_factor(date(2024, 12, 25)), 0.1);
+        assert_eq!(calendar.get_occupancy_factor(date(2024, 12, 24)), 0.9);
+        assert_eq!(calendar.get_occupancy_factor(date(2024, 12, 28)), 0.0);
+    }
+
+    #[test]
+    fn invalid_factors_let_nobody_come() {
+        let mut calendar = Calendar::new(date(2024, 12, 23), date(2024, 12, 29));
+        calendar.set_weekday_factor(f64::NAN);
+        calendar.set_holiday_factor(f64::INFINITY);
+        calendar.add_holiday(date(2024, 12, 25), "Christmas Day".to_string());
+        assert_eq!(calendar.get_occupancy_factor(date(2024, 12, 24)), 0.0);
+        assert_eq!(calendar.get_occupancy_factor(date(2024, 12, 25)), 0.0);
+    }
+}
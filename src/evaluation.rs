@@ -1,24 +1,53 @@
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::io::Write;
-use chrono::{Local, Timelike};
+use std::sync::Arc;
+use chrono::{Datelike, Local, NaiveDate, NaiveTime, Timelike};
 use rubalosim::simulator::event::{EventList, Events};
+use crate::calendar::{Calendar, DayKind};
+use crate::identifier::EventId;
+use crate::room::{Building, DoorSensorKind, RoomType};
 
 pub struct Evaluation {
     // as Wh per year
     energy_consumption_sensor_type: Vec<f64>,
-    // as Watt
-    energy_consumption_light_in_different_states_of_different_rooms: Vec<Vec<f64>>,
+    // as Watt, when the lights are on, dimmed and off
+    energy_consumption_light_of_room_types: HashMap<RoomType, [f64; 3]>,
+    // to get the type of the rooms and to distinguish between rooms and sub rooms
+    building: Arc<Building>,
 }
 
 impl Evaluation {
-    pub fn new(energy_consumption_sensor_type: Vec<f64>, energy_consumption_light_in_different_states_of_different_rooms: Vec<Vec<f64>>) -> Self {
+    /// Creates a new evaluation. The power of the lights of every room is taken from the
+    /// [default profile](RoomType::get_default_profile) of the type of the room, see [Evaluation::set_energy_consumption_light].
+    pub fn new(energy_consumption_sensor_type: Vec<f64>, building: Arc<Building>) -> Self {
+        let mut energy_consumption_light_of_room_types = HashMap::new();
+        for room_type in RoomType::get_all() {
+            energy_consumption_light_of_room_types.insert(room_type, room_type.get_default_profile().lighting_power);
+        }
         return Evaluation {
             energy_consumption_sensor_type,
-            energy_consumption_light_in_different_states_of_different_rooms
+            energy_consumption_light_of_room_types,
+            building
         }
     }
 
-    pub fn calculate_and_write_consumption(&mut self, event_list: &EventList, path:String) {
+    /// Sets the power of the lights in W of all rooms of a specific type, when the lights are on, dimmed and off.
+    pub fn set_energy_consumption_light(&mut self, room_type: RoomType, energy_consumption_light_in_different_states: [f64; 3]) {
+        self.energy_consumption_light_of_room_types.insert(room_type, energy_consumption_light_in_different_states);
+    }
+
+    /// Gets the power of the lights in W of a specific room, when the lights are on, dimmed and off.
+    /// <br/>The lights of a lighting zone have the power of the [type](Building::get_room_type) of its desks.
+    fn get_energy_consumption_light(&self, room_id: &str) -> [f64; 3] {
+        let room_type = self.building.get_room_type(room_id).unwrap_or_default();
+        return self.energy_consumption_light_of_room_types[&room_type];
+    }
+
+    /// Calculates the energy consumption of the lights of every room and sub room and of the sensors during the simulated day
+    /// and writes it into a file in `path`.
+    /// <br/>Returns the energy consumption of all lights and sensors in Wh, e.g. to total it over several days, see [ConsumptionSummary].
+    pub fn calculate_and_write_consumption(&mut self, event_list: &EventList, path:String) -> f64 {
         let mut energy_consumption_sub_rooms_average = 0.0;
         let mut energy_consumption_rooms_average = 0.0;
 
@@ -26,7 +55,6 @@ impl Evaluation {
 
         let mut vec_consumption_per_sub_room:Vec<(String, f64)>= Vec::new();
 
-        // first vector of energy_consumption_light_in_different_states_of_different_rooms is for subrooms the second for normal rooms
         let event_list_copy = event_list.get_event_list_copy();
         let len = event_list_copy.len();
         let start = event_list_copy.first().unwrap().get_time();
@@ -39,14 +67,21 @@ impl Evaluation {
 
             match event.get_action() {
                 Events::Message(message) => {
-                    if event.get_id().contains("SensorType_1") {
+                    let event_id = EventId::parse(event.get_id().as_str());
+                    let sensor_id = match event_id.get_sensor_id() {
+                        Some(sensor_id) => sensor_id,
+                        None => continue
+                    };
+                    if sensor_id.get_sensor_type_id() == "SensorType_1" {
+                        continue
+                    }
+                    // id of the room of the sensor
+                    let message_id = sensor_id.get_room_id().to_string();
+                    // e.g. sensors at doors
+                    if self.building.node_index(message_id.as_str()).is_none() {
                         continue
                     }
-                    let mut message_id = event.get_id();
-                    let index = message_id.find("Sensor_").unwrap()+7;
-                    message_id.replace_range(..index, "");
-                    let index = message_id.find("_no.").unwrap();
-                    message_id.replace_range(index.., "");
+                    let energy_consumption_light = self.get_energy_consumption_light(message_id.as_str());
 
                     let mut action_message = message.clone();
                     let index = action_message.find(":");
@@ -70,16 +105,16 @@ impl Evaluation {
                         let duration = (end-start).num_milliseconds();
 
 
-                        if message_id.contains("sub") {
+                        if self.building.is_sub_room(message_id.as_str()) {
                             let result:f64 = match action_message.as_str() {
                                 "On" => {
-                                    duration as f64 / 1000.0 * self.energy_consumption_light_in_different_states_of_different_rooms[0][0]
+                                    duration as f64 / 1000.0 * energy_consumption_light[0]
                                 },
                                 "Dim" => {
-                                    duration as f64 / 1000.0 * self.energy_consumption_light_in_different_states_of_different_rooms[0][1]
+                                    duration as f64 / 1000.0 * energy_consumption_light[1]
                                 },
                                 "Off" => {
-                                    duration as f64 / 1000.0 * self.energy_consumption_light_in_different_states_of_different_rooms[0][2]
+                                    duration as f64 / 1000.0 * energy_consumption_light[2]
                                 },
                                 _=> {
                                     0.0
@@ -100,13 +135,13 @@ impl Evaluation {
                         } else {
                             let result:f64 = match action_message.as_str() {
                                 "On" => {
-                                    duration as f64 / 1000.0 * self.energy_consumption_light_in_different_states_of_different_rooms[1][0]
+                                    duration as f64 / 1000.0 * energy_consumption_light[0]
                                 },
                                 "Dim" => {
-                                    duration as f64 / 1000.0 * self.energy_consumption_light_in_different_states_of_different_rooms[1][1]
+                                    duration as f64 / 1000.0 * energy_consumption_light[1]
                                 },
                                 "Off" => {
-                                    duration as f64 / 1000.0 * self.energy_consumption_light_in_different_states_of_different_rooms[1][2]
+                                    duration as f64 / 1000.0 * energy_consumption_light[2]
                                 },
                                 _ => 0.0
                             };
@@ -137,6 +172,9 @@ impl Evaluation {
 
         let energy_consumption_sensor_type0 = self.energy_consumption_sensor_type[0] * number_of_sensors / 365.0;
         let energy_consumption_sensor_type1 = self.energy_consumption_sensor_type[1] * 2.0 * number_of_sensors / 365.0;
+        let energy_consumption_lights: f64 = vec_consumption_per_room.iter().chain(vec_consumption_per_sub_room.iter())
+            .map(|(_, consumption)| consumption / 60.0 / 60.0).sum();
+        let energy_consumption = energy_consumption_lights + energy_consumption_sensor_type0 + energy_consumption_sensor_type1;
 
         let date = Local::now();
         let path = path + "Energy_evaluation_" + date.date_naive().to_string().as_str() + "_" + date.time().hour().to_string().as_str() + "_" + date.time().minute().to_string().as_str() + "_" +date.time().second().to_string().as_str() + ".txt";
@@ -173,7 +211,205 @@ impl Evaluation {
             let data = "\n\t".to_owned() + value.0.as_str() + ": " + wh.to_string().as_str();
             f.write(data.as_bytes()).unwrap();
         }
+        return energy_consumption;
+    }
+
+    /// Evaluates the uplink messages of the [door sensors](Building::add_sensors_to_door), that were added by
+    /// [create_door_sensor_events](crate::movement::create_door_sensor_events), and writes the results into a file in `path`:
+    /// - how often every door with a [contact](DoorSensorKind::Contact) was opened,
+    /// - how often every room with a [people counter](DoorSensorKind::PeopleCounter) was entered and left,
+    ///   the highest number of people in the room and how long someone was in the room.
+    ///
+    /// The occupancy of a counted room is estimated from the entries and exits only, so every door of the room needs a counter.
+    pub fn calculate_and_write_door_counts(&self, event_list: &EventList, path: String) {
+        // door and kind of every door sensor, by sensor id
+        let mut door_sensors = HashMap::<String, ((String, String), DoorSensorKind)>::new();
+        for (id_room_1, id_room_2) in self.building.get_doors_with_sensors() {
+            for (kind, sensor) in self.building.get_door_sensors(id_room_1.as_str(), id_room_2.as_str()) {
+                door_sensors.insert(sensor.get_id(), ((id_room_1.clone(), id_room_2.clone()), kind));
+            }
+        }
+
+        let mut openings = BTreeMap::<(String, String), u32>::new();
+        let mut room_counts = BTreeMap::<String, RoomCount>::new();
+        for event in event_list.get_event_list_copy() {
+            let message = match event.get_action() {
+                Events::Message(message) => message,
+                _ => continue
+            };
+            let event_id = EventId::parse(event.get_id().as_str());
+            let (door, kind) = match event_id.get_sensor_id().and_then(|sensor_id| door_sensors.get(&sensor_id.to_string())) {
+                Some(door_sensor) => door_sensor,
+                None => continue
+            };
+            let value = match message.split_once(":") {
+                Some((_, value)) => value.replace(",", ""),
+                None => continue
+            };
+            match kind {
+                DoorSensorKind::Contact => {
+                    if value == "open" {
+                        *openings.entry(door.clone()).or_insert(0) += 1;
+                    }
+                },
+                DoorSensorKind::PeopleCounter(room_id) => {
+                    room_counts.entry(room_id.clone()).or_default().count(value.as_str(), event.get_time());
+                },
+            }
+        }
+
+        let date = Local::now();
+        let path = path + "Door_evaluation_" + date.date_naive().to_string().as_str() + "_" + date.time().hour().to_string().as_str() + "_" + date.time().minute().to_string().as_str() + "_" + date.time().second().to_string().as_str() + ".txt";
+        let mut f = fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path).unwrap();
+
+        f.write_all("Openings per door: ".as_bytes()).unwrap();
+        for ((id_room_1, id_room_2), number) in openings {
+            let data = "\n\t".to_owned() + id_room_1.as_str() + " - " + id_room_2.as_str() + ": " + number.to_string().as_str();
+            f.write_all(data.as_bytes()).unwrap();
+        }
+
+        f.write_all("\n\nEntries, exits, highest occupancy and occupied time in s per counted room: ".as_bytes()).unwrap();
+        for (room_id, room_count) in room_counts {
+            let data = format!("\n\t{}: {}, {}, {}, {}", room_id, room_count.entries, room_count.exits, room_count.highest_occupancy, room_count.occupied_time_in_ms / 1000);
+            f.write_all(data.as_bytes()).unwrap();
+        }
+    }
+}
+
+/// Energy consumption of the simulated days of a [Calendar], see [Evaluation::calculate_and_write_consumption].
+/// <br/>The consumption of a year is extrapolated from the average consumption of the simulated days of every [kind](DayKind),
+/// because weekdays, weekends and holidays differ a lot.
+#[derive(Debug, Clone, Default)]
+pub struct ConsumptionSummary {
+    // date, kind and energy consumption in Wh of every simulated day
+    days: Vec<(NaiveDate, DayKind, f64)>,
+}
+
+impl ConsumptionSummary {
+    pub fn new() -> Self {
+        return ConsumptionSummary {
+            days: Vec::new(),
+        }
+    }
 
+    /// Adds the energy consumption in Wh of a simulated day of the calendar.
+    pub fn add_day(&mut self, calendar: &Calendar, date: NaiveDate, energy_consumption: f64) {
+        self.days.push((date, calendar.get_day_kind(date), energy_consumption));
     }
 
-}
\ No newline at end of file
+    /// Gets the energy consumption in Wh of all simulated days.
+    pub fn get_total(&self) -> f64 {
+        return self.days.iter().map(|(_, _, energy_consumption)| energy_consumption).sum();
+    }
+
+    /// Gets the average energy consumption in Wh of the simulated days of a specific kind.
+    /// <br/>Returns None if no day of the kind was simulated.
+    pub fn get_average(&self, day_kind: DayKind) -> Option<f64> {
+        let consumptions: Vec<f64> = self.days.iter()
+            .filter(|(_, kind, _)| *kind == day_kind)
+            .map(|(_, _, energy_consumption)| *energy_consumption)
+            .collect();
+        if consumptions.is_empty() {
+            return None;
+        }
+        return Some(consumptions.iter().sum::<f64>() / consumptions.len() as f64);
+    }
+
+    /// Extrapolates the energy consumption in Wh of a year, by multiplying the average of every kind of day with the
+    /// [number of days](Calendar::get_number_of_days) of that kind in the year.
+    /// <br/>Returns None if a kind of day occurs in the year, but no day of that kind was simulated.
+    pub fn get_annual_estimate(&self, calendar: &Calendar, year: i32) -> Option<f64> {
+        let mut energy_consumption = 0.0;
+        for day_kind in [DayKind::Weekday, DayKind::Weekend, DayKind::Holiday] {
+            let number_of_days = calendar.get_number_of_days(year, day_kind);
+            if number_of_days > 0 {
+                energy_consumption += self.get_average(day_kind)? * number_of_days as f64;
+            }
+        }
+        return Some(energy_consumption);
+    }
+
+    /// Writes the energy consumption of every simulated day, the total, the averages of the kinds of days and the
+    /// [annual estimate](ConsumptionSummary::get_annual_estimate) for the year of the start of the calendar into a file in `path`.
+    pub fn write(&self, calendar: &Calendar, path: String) {
+        let date = Local::now();
+        let path = path + "Energy_summary_" + date.date_naive().to_string().as_str() + "_" + date.time().hour().to_string().as_str() + "_" + date.time().minute().to_string().as_str() + "_" + date.time().second().to_string().as_str() + ".txt";
+        let mut f = fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path).unwrap();
+
+        f.write_all("Energy consumption per day in Wh: ".as_bytes()).unwrap();
+        for (date, day_kind, energy_consumption) in &self.days {
+            let data = format!("\n\t{} ({:?}): {}", date, day_kind, energy_consumption);
+            f.write_all(data.as_bytes()).unwrap();
+        }
+
+        let data = format!("\n\nTotal energy consumption of {} days in Wh: {}\n", self.days.len(), self.get_total());
+        f.write_all(data.as_bytes()).unwrap();
+
+        f.write_all("\nAverage energy consumption per kind of day in Wh: ".as_bytes()).unwrap();
+        for day_kind in [DayKind::Weekday, DayKind::Weekend, DayKind::Holiday] {
+            let average = match self.get_average(day_kind) {
+                Some(average) => average.to_string(),
+                None => "not simulated".to_string()
+            };
+            let data = format!("\n\t{:?}: {}", day_kind, average);
+            f.write_all(data.as_bytes()).unwrap();
+        }
+
+        let year = calendar.get_start().year();
+        let annual_estimate = match self.get_annual_estimate(calendar, year) {
+            Some(annual_estimate) => annual_estimate.to_string(),
+            None => "not available, not every kind of day was simulated".to_string()
+        };
+        let data = format!("\n\nEstimated energy consumption in {} in Wh: {}\n", year, annual_estimate);
+        f.write_all(data.as_bytes()).unwrap();
+    }
+}
+
+/// Entries and exits of a room with a people counter, see [Evaluation::calculate_and_write_door_counts].
+#[derive(Default)]
+struct RoomCount {
+    entries: u32,
+    exits: u32,
+    // estimated number of people in the room
+    occupancy: u32,
+    highest_occupancy: u32,
+    // time of the entry, after which the room was not empty anymore
+    occupied_since: Option<NaiveTime>,
+    occupied_time_in_ms: i64,
+}
+
+impl RoomCount {
+    /// Counts a message of a people counter, which is either _in_ or _out_.
+    /// <br/>More exits than entries, e.g. of people that were in the room before the simulation, are ignored.
+    fn count(&mut self, direction: &str, time: NaiveTime) {
+        match direction {
+            "in" => {
+                self.entries += 1;
+                if self.occupancy == 0 {
+                    self.occupied_since = Some(time);
+                }
+                self.occupancy += 1;
+                self.highest_occupancy = self.highest_occupancy.max(self.occupancy);
+            },
+            "out" => {
+                self.exits += 1;
+                if self.occupancy == 0 {
+                    return;
+                }
+                self.occupancy -= 1;
+                if self.occupancy == 0 {
+                    if let Some(occupied_since) = self.occupied_since.take() {
+                        self.occupied_time_in_ms += (time - occupied_since).num_milliseconds();
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+}
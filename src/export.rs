@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::fs;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use crate::room::{Building, ConnectionType, RoomKind};
+
+// size of a room in the svg diagram
+const ROOM_WIDTH: f64 = 150.0;
+const ROOM_HEIGHT: f64 = 40.0;
+// space between the rooms and around the floors in the svg diagram
+const SPACING: f64 = 20.0;
+
+/// Style of a room in the exported diagrams, depending on its kind.
+struct RoomStyle {
+    fill: &'static str,
+    dashed: bool,
+    dot_shape: &'static str,
+}
+
+fn room_style(building: &Building, id: &str) -> RoomStyle {
+    if building.is_sub_room(id) {
+        return RoomStyle { fill: "#cfe2f3", dashed: false, dot_shape: "box" };
+    }
+    return match building.get_room_kind(id) {
+        Some(RoomKind::Staircase) => RoomStyle { fill: "#d9d9d9", dashed: false, dot_shape: "hexagon" },
+        Some(RoomKind::Elevator) => RoomStyle { fill: "#d9d9d9", dashed: false, dot_shape: "box3d" },
+        Some(RoomKind::RoomWithoutDoors) => RoomStyle { fill: "#fff2cc", dashed: true, dot_shape: "box" },
+        _ => RoomStyle { fill: "#d9ead3", dashed: false, dot_shape: "box" },
+    };
+}
+
+/// Gets the label of a connection by the id of its edge data, e.g. _Door_ for _Door3_sub_.
+fn connection_label(edge_id: &str) -> &'static str {
+    return match ConnectionType::from_edge_id(edge_id) {
+        Some(ConnectionType::Door) => "Door",
+        Some(ConnectionType::NoDoor) => "NoDoor",
+        Some(ConnectionType::Stairs) => "Stairs",
+        Some(ConnectionType::Shaft) => "Elevator",
+        None => "",
+    };
+}
+
+fn escape(text: &str) -> String {
+    return text.replace("&", "&amp;").replace("\"", "&quot;").replace("<", "&lt;").replace(">", "&gt;");
+}
+
+impl Building {
+    /// Exports the building as graph in the DOT language of [Graphviz](https://graphviz.org/).
+    /// <br/>Every floor is a cluster, rooms are styled by their kind and labeled with their number of sensors,
+    /// connections are labeled with their kind, e.g. _Door_ or _NoDoor_.
+    pub fn to_dot(&self) -> String {
+        let graph = self.get_floors();
+        let mut dot = "graph building {\n    node [style=filled, fontname=\"Helvetica\"];\n    edge [fontname=\"Helvetica\", fontsize=10];\n".to_string();
+        for floor in self.get_levels() {
+            dot += format!("    subgraph cluster_{} {{\n        label=\"Floor {}\";\n", floor.get_id().replace("-", "_"), floor.get_level()).as_str();
+            for id in self.get_rooms_on_floor(floor.get_level()) {
+                let index = self.node_index(id.as_str()).unwrap();
+                let style = room_style(self, id.as_str());
+                let mut style_attribute = "filled".to_string();
+                if style.dashed {
+                    style_attribute += ",dashed";
+                }
+                dot += format!("        \"{}\" [label=\"{}\\n{} sensors\", shape={}, style=\"{}\", fillcolor=\"{}\"];\n",
+                               id.replace("\"", "\\\""), id.replace("\"", "\\\""), graph[index].get_sensors().len(), style.dot_shape, style_attribute, style.fill).as_str();
+            }
+            dot += "    }\n";
+        }
+        for edge in graph.edge_references() {
+            let label = connection_label(edge.weight().get_data().get_id().as_str());
+            let mut attributes = format!("label=\"{}\"", label);
+            if label == "NoDoor" {
+                attributes += ", style=dashed";
+            }
+            dot += format!("    \"{}\" -- \"{}\" [{}];\n",
+                           graph[edge.source()].get_data().get_id().replace("\"", "\\\""),
+                           graph[edge.target()].get_data().get_id().replace("\"", "\\\""), attributes).as_str();
+        }
+        dot += "}\n";
+        return dot;
+    }
+
+    /// Writes the [DOT export](Building::to_dot) of the building to the file at `path`.
+    pub fn write_dot(&self, path: String) -> Result<(), std::io::Error> {
+        return fs::write(path, self.to_dot());
+    }
+
+    /// Exports the building as SVG floor diagram.
+    /// <br/>Every floor is drawn as a band, with the highest floor on top. The rooms of a floor are placed in a row,
+    /// their sub rooms are placed in a column below them. Rooms are styled by their kind and labeled with their number
+    /// of sensors, connections are drawn as lines, connections without doors as dashed lines.
+    pub fn to_svg(&self) -> String {
+        let graph = self.get_floors();
+        let (positions, floor_bands, width) = self.get_diagram_layout();
+        let height = floor_bands.iter().map(|(_, y, band_height)| y + band_height).fold(0.0, f64::max);
+
+        let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"Helvetica\" font-size=\"12\">\n", width, height);
+        for (level, y, band_height) in &floor_bands {
+            svg += format!("  <rect x=\"0\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#ffffff\" stroke=\"#666666\"/>\n", y, width, band_height).as_str();
+            svg += format!("  <text x=\"{}\" y=\"{}\" font-weight=\"bold\">Floor {}</text>\n", SPACING / 2.0, y + SPACING * 0.75, level).as_str();
+        }
+
+        for edge in graph.edge_references() {
+            let source = positions[&edge.source()];
+            let target = positions[&edge.target()];
+            let label = connection_label(edge.weight().get_data().get_id().as_str());
+            let mut dash = "";
+            if label == "NoDoor" {
+                dash = " stroke-dasharray=\"5,5\"";
+            }
+            svg += format!("  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#333333\"{}/>\n", source.0, source.1, target.0, target.1, dash).as_str();
+            svg += format!("  <text x=\"{}\" y=\"{}\" font-size=\"9\" fill=\"#666666\">{}</text>\n", (source.0 + target.0) / 2.0 + 2.0, (source.1 + target.1) / 2.0 - 2.0, label).as_str();
+        }
+
+        for index in graph.node_indices() {
+            let id = graph[index].get_data().get_id();
+            let (x, y) = positions[&index];
+            let style = room_style(self, id.as_str());
+            let mut dash = "";
+            if style.dashed {
+                dash = " stroke-dasharray=\"4,2\"";
+            }
+            svg += format!("  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"4\" fill=\"{}\" stroke=\"#333333\"{}/>\n",
+                           x - ROOM_WIDTH / 2.0, y - ROOM_HEIGHT / 2.0, ROOM_WIDTH, ROOM_HEIGHT, style.fill, dash).as_str();
+            svg += format!("  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n", x, y - 2.0, escape(id.as_str())).as_str();
+            svg += format!("  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-size=\"10\">{} sensors</text>\n", x, y + 12.0, graph[index].get_sensors().len()).as_str();
+        }
+        svg += "</svg>\n";
+        return svg;
+    }
+
+    /// Writes the [SVG export](Building::to_svg) of the building to the file at `path`.
+    pub fn write_svg(&self, path: String) -> Result<(), std::io::Error> {
+        return fs::write(path, self.to_svg());
+    }
+
+    /// Gets the layout of the svg diagram: the center of every room, the level, y coordinate and height
+    /// of the band of every floor and the width of the whole diagram.
+    fn get_diagram_layout(&self) -> (HashMap<NodeIndex, (f64, f64)>, Vec<(i32, f64, f64)>, f64) {
+        let mut positions = HashMap::<NodeIndex, (f64, f64)>::new();
+        let mut floor_bands = Vec::<(i32, f64, f64)>::new();
+        let mut width = SPACING * 2.0 + ROOM_WIDTH;
+        let mut levels: Vec<i32> = self.get_levels().iter().map(|floor| floor.get_level()).collect();
+        levels.reverse();
+        let mut floor_y = 0.0;
+        for level in levels {
+            let rooms = self.get_rooms_on_floor(level);
+            let mut column = 0;
+            let mut max_rows = 0;
+            for id in &rooms {
+                if self.is_sub_room(id.as_str()) {
+                    continue
+                }
+                // the room and all of its sub rooms, each directly below its parent
+                let mut column_ids = vec![id.clone()];
+                let mut row = 0;
+                while row < column_ids.len() {
+                    let parent = column_ids[row].clone();
+                    let children: Vec<String> = self.children(parent.as_str()).into_iter()
+                        .filter(|child| rooms.contains(child))
+                        .collect();
+                    for (offset, child) in children.into_iter().enumerate() {
+                        column_ids.insert(row + 1 + offset, child);
+                    }
+                    row += 1;
+                }
+                for (row, column_id) in column_ids.iter().enumerate() {
+                    let x = SPACING + column as f64 * (ROOM_WIDTH + SPACING) + ROOM_WIDTH / 2.0;
+                    let y = floor_y + SPACING * 1.5 + row as f64 * (ROOM_HEIGHT + SPACING) + ROOM_HEIGHT / 2.0;
+                    positions.insert(self.node_index(column_id.as_str()).unwrap(), (x, y));
+                }
+                max_rows = max_rows.max(column_ids.len());
+                column += 1;
+            }
+            // sub rooms whose parent is on another floor or does not exist
+            for id in &rooms {
+                let index = self.node_index(id.as_str()).unwrap();
+                if positions.contains_key(&index) {
+                    continue
+                }
+                let x = SPACING + column as f64 * (ROOM_WIDTH + SPACING) + ROOM_WIDTH / 2.0;
+                positions.insert(index, (x, floor_y + SPACING * 1.5 + ROOM_HEIGHT / 2.0));
+                max_rows = max_rows.max(1);
+                column += 1;
+            }
+            let band_height = SPACING * 2.5 + max_rows as f64 * (ROOM_HEIGHT + SPACING);
+            floor_bands.push((level, floor_y, band_height));
+            width = width.max(SPACING + column as f64 * (ROOM_WIDTH + SPACING));
+            floor_y += band_height;
+        }
+        return (positions, floor_bands, width);
+    }
+}
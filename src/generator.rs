@@ -0,0 +1,248 @@
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use chrono::Duration;
+use petgraph::graph::NodeIndex;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rubalosim::sensor::SensorType;
+use crate::builder::BuildingBuilder;
+use crate::room::{Building, BuildingError, Coordinates, Orientation, RoomProperties, RoomType};
+
+// length of a corridor, distance between the offices and height of a floor in m
+const CORRIDOR_LENGTH: f64 = 30.0;
+const OFFICE_DISTANCE: f64 = 5.0;
+const FLOOR_HEIGHT: f64 = 3.5;
+// window area of an office in relation to its floor area
+const WINDOW_RATIO: f64 = 0.2;
+
+/// Parameters of a [generated building](generate_building).
+#[derive(Debug, Clone)]
+pub struct GeneratorParameters {
+    pub number_of_floors: u32,
+    pub corridors_per_floor: u32,
+    pub offices_per_corridor: u32,
+    /// Share of the offices that are connected with their corridor via a door, between 0 and 1.
+    pub door_ratio: f64,
+    /// Number of staircases on every floor.
+    pub number_of_staircases: u32,
+    /// Sensors of every corridor and office.
+    pub sensors_per_room: Vec<(u32, SensorType)>,
+    /// Time it takes to get from one floor to the next one via the stairs.
+    pub stairs_travel_time: Duration,
+    /// The same seed and parameters always generate the same building.
+    pub seed: u64,
+}
+
+impl GeneratorParameters {
+    /// Creates the parameters of a building with one floor, three corridors with eight offices each and
+    /// two staircases, where 80% of the offices have a door.
+    pub fn new(sensors_per_room: Vec<(u32, SensorType)>, seed: u64) -> Self {
+        return GeneratorParameters {
+            number_of_floors: 1,
+            corridors_per_floor: 3,
+            offices_per_corridor: 8,
+            door_ratio: 0.8,
+            number_of_staircases: 2,
+            sensors_per_room,
+            stairs_travel_time: Duration::seconds(20),
+            seed,
+        }
+    }
+}
+
+/// Errors that can occur while [generating a building](generate_building).
+#[derive(Debug)]
+pub enum GeneratorError {
+    /// The share of the offices with a door is not between 0 and 1, or NaN.
+    InvalidDoorRatio,
+    /// The time it takes to get from one floor to the next one is negative.
+    NegativeTravelTime,
+    /// The generated building is invalid.
+    Building(BuildingError),
+}
+
+impl Display for GeneratorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            GeneratorError::InvalidDoorRatio => write!(f, "the door ratio has to be between 0 and 1"),
+            GeneratorError::NegativeTravelTime => write!(f, "the travel time of the stairs can not be negative"),
+            GeneratorError::Building(error) => write!(f, "invalid building: {}", error),
+        };
+    }
+}
+
+impl std::error::Error for GeneratorError {}
+
+impl From<BuildingError> for GeneratorError {
+    fn from(error: BuildingError) -> Self {
+        return GeneratorError::Building(error);
+    }
+}
+
+/// Checks the parameters before anything is generated.
+fn check_parameters(parameters: &GeneratorParameters) -> Result<(), GeneratorError> {
+    // NaN is not contained in any range
+    if !(0.0..=1.0).contains(&parameters.door_ratio) {
+        return Err(GeneratorError::InvalidDoorRatio);
+    }
+    if parameters.stairs_travel_time < Duration::zero() {
+        return Err(GeneratorError::NegativeTravelTime);
+    }
+    return Ok(());
+}
+
+/// Gets the id of the room that was added last.
+fn last_room_id(building: &Building) -> String {
+    return building.get_floors()[NodeIndex::new(building.get_number_of_rooms() - 1)].get_data().get_id();
+}
+
+/// Generates a random office building.
+/// <br/>Every floor has the same layout: the corridors form a row, in which neighbouring corridors are connected
+/// without doors and sometimes a corridor is connected with the one after the next, too. The offices are
+/// [sub rooms](Building::add_sub_rooms_with_doors) of the corridors, lying on both sides of them, neighbouring offices
+/// are sometimes connected via a door. Every floor has a kitchen and a restroom and some offices are meeting rooms.
+/// The staircases are placed at random corridors and are connected with the staircases of the floors above and below.
+/// <br/>All rooms get [coordinates](Coordinates) and [properties](RoomProperties), so that the lengths of the connections are known.
+/// <br/>Returns the building and the offspring number of its last sensor, see [BuildingBuilder::build],
+/// or an error if the door ratio is not between 0 and 1 or the travel time of the stairs is negative.
+pub fn generate_building(parameters: &GeneratorParameters) -> Result<(Arc<Building>, i64), GeneratorError> {
+    check_parameters(parameters)?;
+    let mut rng = StdRng::seed_from_u64(parameters.seed);
+    let mut builder = BuildingBuilder::new();
+    let corridors = parameters.corridors_per_floor.max(1);
+    // corridors of the staircases and cross connections between corridors, the same on every floor
+    let staircase_corridors: Vec<u32> = (0..parameters.number_of_staircases).map(|_| rng.gen_range(0..corridors)).collect();
+    let cross_connections: Vec<bool> = (0..corridors).map(|_| rng.gen_bool(0.2)).collect();
+
+    for level in 0..parameters.number_of_floors as i32 {
+        if level != 0 {
+            builder.add_floor(level)?;
+        }
+        builder.set_current_floor(level)?;
+        let z = level as f64 * FLOOR_HEIGHT;
+
+        let mut corridor_ids = Vec::<String>::new();
+        for corridor in 0..corridors {
+            builder.add_room_without_doors(corridor as i32, parameters.sensors_per_room.clone(), false)?;
+            let id = last_room_id(builder.get_building());
+            let x = corridor as f64 * CORRIDOR_LENGTH;
+            builder.set_coordinates(id.as_str(), Coordinates::new(x, 0.0, z))?;
+            builder.set_room_properties(id.as_str(), RoomProperties::new(CORRIDOR_LENGTH * 2.0, 3.0))?;
+            corridor_ids.push(id);
+        }
+        for corridor in 1..corridors as usize {
+            builder.new_no_door_connection(corridor_ids[corridor - 1].clone(), corridor_ids[corridor].clone(), corridor as i32)?;
+            if (corridor + 1 < corridors as usize) & cross_connections[corridor] {
+                builder.new_no_door_connection(corridor_ids[corridor - 1].clone(), corridor_ids[corridor + 1].clone(), (corridors as usize + corridor) as i32)?;
+            }
+        }
+
+        for (staircase, corridor) in staircase_corridors.iter().enumerate() {
+            builder.add_staircase(staircase as i32)?;
+            let id = last_room_id(builder.get_building());
+            let x = *corridor as f64 * CORRIDOR_LENGTH;
+            builder.set_coordinates(id.as_str(), Coordinates::new(x, -OFFICE_DISTANCE, z))?;
+            builder.set_room_properties(id.as_str(), RoomProperties::new(15.0, FLOOR_HEIGHT))?;
+            builder.new_door_connection(id, corridor_ids[*corridor as usize].clone(), staircase as i32)?;
+        }
+
+        let mut office_ids = Vec::<String>::new();
+        for corridor_id in &corridor_ids {
+            let number_with_doors = (0..parameters.offices_per_corridor).filter(|_| rng.gen_bool(parameters.door_ratio)).count() as i32;
+            builder.add_sub_rooms_with_doors(number_with_doors, corridor_id.clone(), parameters.sensors_per_room.clone(), true)?;
+            builder.add_sub_rooms_without_doors(parameters.offices_per_corridor as i32 - number_with_doors, corridor_id.clone(), parameters.sensors_per_room.clone(), true)?;
+            let mut offices = builder.get_building().children(corridor_id.as_str());
+            offices.shuffle(&mut rng);
+            let corridor_coordinates = builder.get_building().get_coordinates(corridor_id.as_str()).unwrap();
+            // offices are placed alternately on both sides of the corridor
+            for (position, office_id) in offices.iter().enumerate() {
+                let x = corridor_coordinates.x - CORRIDOR_LENGTH / 2.0 + (position / 2) as f64 * OFFICE_DISTANCE;
+                let y = if position % 2 == 0 { OFFICE_DISTANCE } else { -OFFICE_DISTANCE };
+                builder.set_coordinates(office_id.as_str(), Coordinates::new(x, y, z))?;
+                let room_type = if rng.gen_bool(0.1) { RoomType::MeetingRoom } else { RoomType::Office };
+                let orientation = if position % 2 == 0 { Orientation::North } else { Orientation::South };
+                let floor_area = rng.gen_range(12.0..30.0);
+                builder.set_room_properties(office_id.as_str(), RoomProperties::new(floor_area, 3.0).with_windows(floor_area * WINDOW_RATIO, orientation))?;
+                builder.set_room_type(office_id.as_str(), room_type)?;
+                if (position >= 2) & rng.gen_bool(0.15) {
+                    builder.new_door_connection(offices[position - 2].clone(), office_id.clone(), position as i32)?;
+                }
+            }
+            office_ids.extend(offices);
+        }
+
+        // every floor has a kitchen and a restroom
+        let mut special_rooms = office_ids.choose_multiple(&mut rng, 2);
+        if let Some(kitchen) = special_rooms.next() {
+            builder.set_room_type(kitchen.as_str(), RoomType::Kitchen)?;
+        }
+        if let Some(restroom) = special_rooms.next() {
+            builder.set_room_type(restroom.as_str(), RoomType::Restroom)?;
+        }
+    }
+
+    for level in 1..parameters.number_of_floors as i32 {
+        for staircase in 0..parameters.number_of_staircases as i32 {
+            builder.connect_staircases(staircase, level - 1, level, parameters.stairs_travel_time)?;
+        }
+    }
+    builder.set_current_floor(0)?;
+    return Ok(builder.build());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::sensor_types;
+    use super::*;
+
+    /// Describes the rooms of a building with their coordinates, properties and types and the connections in graph order.
+    fn describe(building: &Building) -> (Vec<String>, Vec<(usize, usize, String)>) {
+        let graph = building.get_floors();
+        let rooms = graph.node_indices().map(|node| {
+            let id = graph[node].get_data().get_id();
+            return format!("{} {:?} {:?} {:?}", id, building.get_coordinates(id.as_str()), building.get_room_properties(id.as_str()), building.get_room_type(id.as_str()));
+        }).collect();
+        let connections = graph.edge_indices().map(|edge| {
+            let (source, target) = graph.edge_endpoints(edge).unwrap();
+            return (source.index(), target.index(), graph[edge].get_data().get_id());
+        }).collect();
+        return (rooms, connections);
+    }
+
+    fn create_parameters(seed: u64) -> GeneratorParameters {
+        let types = sensor_types();
+        let mut parameters = GeneratorParameters::new(vec![(1, types[0].clone()), (1, types[1].clone())], seed);
+        parameters.number_of_floors = 3;
+        return parameters;
+    }
+
+    #[test]
+    fn the_same_seed_generates_the_same_building() {
+        let (building_1, offspring_number_1) = generate_building(&create_parameters(7)).unwrap();
+        let (building_2, offspring_number_2) = generate_building(&create_parameters(7)).unwrap();
+        assert_eq!(describe(&building_1), describe(&building_2));
+        assert_eq!(offspring_number_1, offspring_number_2);
+        let (building_3, _) = generate_building(&create_parameters(8)).unwrap();
+        assert_ne!(describe(&building_1), describe(&building_3));
+    }
+
+    #[test]
+    fn generated_buildings_are_valid() {
+        let (building, _) = generate_building(&create_parameters(7)).unwrap();
+        assert_eq!(building.get_levels().len(), 3);
+        assert_eq!(building.validate().get_problems(), &Vec::new());
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        let mut parameters = create_parameters(7);
+        parameters.door_ratio = f64::NAN;
+        assert!(matches!(generate_building(&parameters), Err(GeneratorError::InvalidDoorRatio)));
+        parameters.door_ratio = 1.5;
+        assert!(matches!(generate_building(&parameters), Err(GeneratorError::InvalidDoorRatio)));
+        parameters.door_ratio = 1.0;
+        parameters.stairs_travel_time = Duration::seconds(-1);
+        assert!(matches!(generate_building(&parameters), Err(GeneratorError::NegativeTravelTime)));
+    }
+}
@@ -1,26 +1,262 @@
-use chrono::{Duration, NaiveTime};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use chrono::{Duration, NaiveTime, Timelike};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
 use rubalosim::movable_object::MovableObjects;
+use crate::room::{Building, BuildingError, RoomType};
+use crate::schedule::Schedule;
+
+/// Distribution of the time, at which a movable object arrives or leaves.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeDistribution {
+    /// Always the same time.
+    Fixed(NaiveTime),
+    /// Normal distribution around a mean, e.g. around 08:00 with a standard deviation of 30 minutes.
+    Normal { mean: NaiveTime, standard_deviation: Duration },
+    /// Every time between start and end is equally likely.
+    Uniform { start: NaiveTime, end: NaiveTime },
+    /// Empirical histogram: every bin starts at a specific time, lasts `bin_width` and has a weight,
+    /// e.g. the share of the people that arrive in it. Within a bin every time is equally likely.
+    Empirical { bins: Vec<(NaiveTime, f64)>, bin_width: Duration },
+}
+
+impl TimeDistribution {
+    /// Draws a time from the distribution. Times are limited to the day they were drawn on, from 00:00:00 to 23:59:59.
+    /// <br/>An empirical histogram without positive weights always gives the start of its first bin, or 00:00:00 if it has no bins.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> NaiveTime {
+        let seconds = match self {
+            TimeDistribution::Fixed(time) => seconds_from_midnight(*time),
+            TimeDistribution::Normal { mean, standard_deviation } => {
+                // Box-Muller transform
+                let u1: f64 = 1.0 - rng.gen::<f64>();
+                let u2: f64 = rng.gen::<f64>();
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                seconds_from_midnight(*mean) + z * standard_deviation.num_milliseconds() as f64 / 1000.0
+            },
+            TimeDistribution::Uniform { start, end } => {
+                let (start, end) = (seconds_from_midnight(*start), seconds_from_midnight(*end));
+                if start < end { rng.gen_range(start..end) } else { start }
+            },
+            TimeDistribution::Empirical { bins, bin_width } => {
+                match WeightedIndex::new(bins.iter().map(|(_, weight)| weight.max(0.0))) {
+                    Ok(distribution) => {
+                        let start = seconds_from_midnight(bins[distribution.sample(rng)].0);
+                        start + rng.gen::<f64>() * bin_width.num_milliseconds() as f64 / 1000.0
+                    },
+                    Err(_) => bins.first().map(|(start, _)| seconds_from_midnight(*start)).unwrap_or(0.0)
+                }
+            },
+        };
+        let seconds = seconds.clamp(0.0, 86399.0);
+        return NaiveTime::from_num_seconds_from_midnight_opt(seconds as u32, ((seconds.fract()) * 1e9) as u32).unwrap();
+    }
+
+    /// Gets the earliest and the latest time, that can be [drawn](TimeDistribution::sample) from the distribution.
+    /// <br/>A normal distribution can give every time of the day, unless its standard deviation is zero.
+    pub fn get_bounds(&self) -> (NaiveTime, NaiveTime) {
+        let end_of_day = NaiveTime::from_hms_opt(23, 59, 59).unwrap();
+        return match self {
+            TimeDistribution::Fixed(time) => (*time, *time),
+            TimeDistribution::Normal { mean, standard_deviation } => {
+                if standard_deviation.is_zero() { (*mean, *mean) } else { (NaiveTime::MIN, end_of_day) }
+            },
+            TimeDistribution::Uniform { start, end } => (*start, (*end).max(*start)),
+            TimeDistribution::Empirical { bins, bin_width } => {
+                let starts: Vec<NaiveTime> = bins.iter().filter(|(_, weight)| *weight > 0.0).map(|(start, _)| *start).collect();
+                match (starts.iter().min(), starts.iter().max()) {
+                    (Some(first), Some(last)) => {
+                        let (end, overflow) = last.overflowing_add_signed(*bin_width);
+                        (*first, if overflow == 0 { end.min(end_of_day) } else { end_of_day })
+                    },
+                    _ => {
+                        let start = bins.first().map(|(start, _)| *start).unwrap_or(NaiveTime::MIN);
+                        (start, start)
+                    }
+                }
+            },
+        };
+    }
+}
+
+/// Gets the seconds since midnight of a time.
+fn seconds_from_midnight(time: NaiveTime) -> f64 {
+    return time.num_seconds_from_midnight() as f64 + time.nanosecond() as f64 / 1e9;
+}
 
 /// Representation of a individual. It can move to specific points in the building
 /// with a specific static speed.
+/// <br/>If a walking speed is set, the time of a move depends on the length of the connection between the rooms,
+/// see [Individual::get_movement_time].
+/// <br/>Several individuals with different parameters form a [Population], e.g. staff, visitors and cleaners.
+#[derive(Debug, Clone)]
 pub struct Individual {
+    name: String,
     number_of_movable_objects: u32,
     time_of_creation: NaiveTime,
     time_of_deletion: NaiveTime,
     number_of_random_moves: u32,
     speed: Duration,
+    // in m/s
+    walking_speed: Option<f64>,
+    // weights of the destinations of specific room types, 1.0 if not set
+    room_affinities: HashMap<RoomType, f64>,
+    // distributions of the times of arrival and departure, None if everyone arrives and leaves at the same time
+    arrival: Option<TimeDistribution>,
+    departure: Option<TimeDistribution>,
+    // daily schedule, None if the movable objects move randomly
+    schedule: Option<Schedule>,
+    // whether every movable object gets a home room automatically, see Population::get_home_rooms
+    has_home_rooms: bool,
 }
 
 impl Individual {
     pub fn new(number_of_movable_objects: u32, time_of_creation: NaiveTime, time_of_deletion: NaiveTime, number_of_random_moves: u32, speed: Duration) -> Individual {
         return Individual {
+            name: "Individual".to_string(),
             number_of_movable_objects,
             time_of_creation,
             time_of_deletion,
             number_of_random_moves,
             speed,
+            walking_speed: None,
+            room_affinities: HashMap::new(),
+            arrival: None,
+            departure: None,
+            schedule: None,
+            has_home_rooms: false,
         }
     }
+
+    /// Sets whether every movable object gets a home room, e.g. its office, see [Population::get_home_rooms].
+    pub fn set_has_home_rooms(&mut self, has_home_rooms: bool) {
+        self.has_home_rooms = has_home_rooms;
+    }
+
+    pub fn has_home_rooms(&self) -> bool {
+        return self.has_home_rooms;
+    }
+
+    /// Gets the [destinations](Building::get_destinations) of the building with their weights for the movable objects,
+    /// which are their attractiveness multiplied by the [room affinity](Individual::get_room_affinity) of the type of the room.
+    pub fn get_destination_weights(&self, building: &Building) -> Vec<(String, f64)> {
+        return building.get_destinations().into_iter()
+            .map(|(id, attractiveness)| {
+                let weight = attractiveness as f64 * self.get_room_affinity(building.get_room_type(id.as_str()).unwrap_or_default());
+                (id, weight)
+            })
+            .collect();
+    }
+
+    /// Sets the daily schedule of the movable objects, which replaces their random moves.
+    pub fn set_schedule(&mut self, schedule: Schedule) {
+        self.schedule = Some(schedule);
+    }
+
+    pub fn get_schedule(&self) -> Option<&Schedule> {
+        return self.schedule.as_ref();
+    }
+
+    /// Sets the distribution of the times at which the movable objects arrive, instead of the time of creation.
+    pub fn set_arrival_distribution(&mut self, distribution: TimeDistribution) {
+        self.arrival = Some(distribution);
+    }
+
+    pub fn get_arrival_distribution(&self) -> Option<&TimeDistribution> {
+        return self.arrival.as_ref();
+    }
+
+    /// Sets the distribution of the times at which the movable objects leave, instead of the time of deletion.
+    pub fn set_departure_distribution(&mut self, distribution: TimeDistribution) {
+        self.departure = Some(distribution);
+    }
+
+    pub fn get_departure_distribution(&self) -> Option<&TimeDistribution> {
+        return self.departure.as_ref();
+    }
+
+    /// Draws the times of arrival and departure of one movable object from the [distributions](TimeDistribution),
+    /// or gives the time of creation and deletion if no distribution is set.
+    /// <br/>The departure is never before the arrival.
+    pub fn sample_times<R: Rng>(&self, rng: &mut R) -> (NaiveTime, NaiveTime) {
+        let arrival = match &self.arrival {
+            Some(distribution) => distribution.sample(rng),
+            None => self.time_of_creation
+        };
+        let departure = match &self.departure {
+            Some(distribution) => distribution.sample(rng),
+            None => self.time_of_deletion
+        };
+        return (arrival, departure.max(arrival));
+    }
+
+    /// Gets the earliest time at which a movable object can arrive, see [Individual::sample_times].
+    pub fn get_earliest_arrival(&self) -> NaiveTime {
+        return match &self.arrival {
+            Some(distribution) => distribution.get_bounds().0,
+            None => self.time_of_creation
+        };
+    }
+
+    /// Gets the latest time at which a movable object can leave, see [Individual::sample_times].
+    pub fn get_latest_departure(&self) -> NaiveTime {
+        let latest_departure = match &self.departure {
+            Some(distribution) => distribution.get_bounds().1,
+            None => self.time_of_deletion
+        };
+        // the departure is never before the arrival
+        let latest_arrival = match &self.arrival {
+            Some(distribution) => distribution.get_bounds().1,
+            None => self.time_of_creation
+        };
+        return latest_departure.max(latest_arrival);
+    }
+
+    /// Sets the name of the group of movable objects, e.g. _Staff_ or _Visitors_.
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn get_name(&self) -> String {
+        return self.name.clone();
+    }
+
+    /// Sets how much the movable objects prefer destinations of a specific room type, e.g. 5.0 for meeting rooms
+    /// and 0.0 for offices in case of visitors. The attractiveness of a destination is multiplied by the affinity of its type.
+    pub fn set_room_affinity(&mut self, room_type: RoomType, affinity: f64) {
+        self.room_affinities.insert(room_type, affinity.max(0.0));
+    }
+
+    /// Gets the affinity to a specific room type, which is 1.0 if it was not set.
+    pub fn get_room_affinity(&self, room_type: RoomType) -> f64 {
+        return self.room_affinities.get(&room_type).cloned().unwrap_or(1.0);
+    }
+
+    /// Sets the walking speed in m/s, e.g. 1.4 for an average adult.
+    pub fn set_walking_speed(&mut self, walking_speed: f64) {
+        self.walking_speed = Some(walking_speed);
+    }
+
+    pub fn get_walking_speed(&self) -> Option<f64> {
+        return self.walking_speed;
+    }
+
+    /// Gets the time it takes to move between two connected rooms.
+    /// <br/>Between floors this is the [travel time](Building::get_travel_time) of the stairs or the elevator,
+    /// otherwise the [length of the connection](Building::get_connection_length) divided by the walking speed.
+    /// If the length or the walking speed is unknown, the static speed is used.
+    pub fn get_movement_time(&self, building: &Building, id_room_1: &str, id_room_2: &str) -> Duration {
+        if let Some(travel_time) = building.get_travel_time(id_room_1, id_room_2) {
+            return travel_time;
+        }
+        let length = building.get_connection_length(id_room_1, id_room_2);
+        if let (Some(length), Some(walking_speed)) = (length, self.walking_speed) {
+            if walking_speed > 0.0 {
+                return Duration::milliseconds((length / walking_speed * 1000.0).round() as i64);
+            }
+        }
+        return self.speed;
+    }
 }
 
 impl MovableObjects for Individual {
@@ -33,15 +269,196 @@ impl MovableObjects for Individual {
     }
 
     fn get_time_of_creation(&self) -> NaiveTime {
-        self.time_of_creation.clone()
+        return self.get_earliest_arrival();
     }
 
     fn get_time_of_deletion(&self) -> NaiveTime {
-        self.time_of_deletion.clone()
+        return self.get_latest_departure();
     }
 
     fn get_speed(&self) -> Duration {
         self.speed
     }
 
-}
\ No newline at end of file
+}
+
+/// Errors that can occur while setting up a [Population].
+#[derive(Debug)]
+pub enum PopulationError {
+    /// No group of the population contains a movable object with this number.
+    UnknownMovableObject(usize),
+    /// The room does not exist in the building.
+    Building(BuildingError),
+}
+
+impl Display for PopulationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            PopulationError::UnknownMovableObject(movable_object) => write!(f, "unknown movable object: {}", movable_object),
+            PopulationError::Building(error) => write!(f, "invalid room: {}", error),
+        };
+    }
+}
+
+impl std::error::Error for PopulationError {}
+
+impl From<BuildingError> for PopulationError {
+    fn from(error: BuildingError) -> Self {
+        return PopulationError::Building(error);
+    }
+}
+
+/// Several groups of movable objects with different parameters in one simulation, e.g. staff, part-timers, visitors and cleaners.
+/// <br/>The movable objects are numbered group by group in the order the groups were added.
+/// <br/>The simulator only knows one kind of movable objects, so it gets the total number of objects, the
+/// [earliest arrival](Individual::get_earliest_arrival), the [latest departure](Individual::get_latest_departure),
+/// the highest number of random moves and the lowest static speed of all groups.
+/// The moves of every group are created by [apply_population](crate::movement::apply_population).
+#[derive(Debug, Clone)]
+pub struct Population {
+    groups: Vec<Individual>,
+    // the same seed and groups always create the same moves
+    seed: u64,
+    // home rooms, that were set explicitly, by the number of the movable object
+    home_rooms: HashMap<usize, String>,
+}
+
+impl Population {
+    /// Creates a population without groups.
+    pub fn new(seed: u64) -> Self {
+        return Population {
+            groups: Vec::new(),
+            seed,
+            home_rooms: HashMap::new(),
+        }
+    }
+
+    /// Sets the home room of a movable object by its number, e.g. its office, where it spends most of its time.
+    /// <br/>This works for movable objects of every group, not only the ones that [have home rooms](Individual::has_home_rooms),
+    /// but the group of the movable object has to be added before.
+    /// <br/>Returns an error if no group contains the movable object or if the room does not exist in the building.
+    pub fn set_home_room(&mut self, building: &Building, movable_object: usize, room_id: String) -> Result<(), PopulationError> {
+        if self.get_group_of_movable_object(movable_object).is_none() {
+            return Err(PopulationError::UnknownMovableObject(movable_object));
+        }
+        building.find_node_index(room_id.as_str())?;
+        self.home_rooms.insert(movable_object, room_id);
+        return Ok(());
+    }
+
+    /// Gets the home room of every movable object, or None if it has no home room.
+    /// <br/>Movable objects, whose home room was [set](Population::set_home_room), keep it. The other movable objects of the groups,
+    /// that [have home rooms](Individual::has_home_rooms), get one of the [destinations](Building::get_destinations) with a
+    /// [positive weight](Individual::get_destination_weights) for their group, one after the other the one with the fewest movable objects,
+    /// so that the movable objects are spread evenly. If several destinations have the fewest movable objects, the first one is chosen.
+    pub fn get_home_rooms(&self, building: &Building) -> Vec<Option<String>> {
+        let number_of_movable_objects = self.get_number_of_movable_objects() as usize;
+        let mut home_rooms: Vec<Option<String>> = (0..number_of_movable_objects).map(|movable_object| self.home_rooms.get(&movable_object).cloned()).collect();
+        let mut occupants = HashMap::<String, usize>::new();
+        for room_id in home_rooms.iter().flatten() {
+            *occupants.entry(room_id.clone()).or_default() += 1;
+        }
+        let mut movable_object = 0;
+        for group in &self.groups {
+            let candidates: Vec<String> = group.get_destination_weights(building).into_iter()
+                .filter(|(_, weight)| *weight > 0.0)
+                .map(|(id, _)| id)
+                .collect();
+            for _ in 0..group.number_of_movable_objects {
+                if group.has_home_rooms & home_rooms[movable_object].is_none() {
+                    let room_id = candidates.iter().min_by_key(|id| occupants.get(*id).cloned().unwrap_or(0));
+                    if let Some(room_id) = room_id {
+                        *occupants.entry(room_id.clone()).or_default() += 1;
+                        home_rooms[movable_object] = Some(room_id.clone());
+                    }
+                }
+                movable_object += 1;
+            }
+        }
+        return home_rooms;
+    }
+
+    pub fn add_group(&mut self, group: Individual) {
+        self.groups.push(group);
+    }
+
+    pub fn get_groups(&self) -> &Vec<Individual> {
+        return &self.groups;
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        return self.seed;
+    }
+
+    /// Gets the group of a movable object by its number.
+    /// <br/>Returns None if the number is not smaller than the number of all movable objects.
+    pub fn get_group_of_movable_object(&self, movable_object: usize) -> Option<&Individual> {
+        let mut first = 0;
+        for group in &self.groups {
+            first += group.number_of_movable_objects as usize;
+            if movable_object < first {
+                return Some(group);
+            }
+        }
+        return None;
+    }
+}
+
+impl MovableObjects for Population {
+    fn get_number_of_movable_objects(&self) -> u32 {
+        return self.groups.iter().map(|group| group.number_of_movable_objects).sum();
+    }
+
+    fn get_number_of_random_moves(&self) -> u32 {
+        return self.groups.iter().map(|group| group.number_of_random_moves).max().unwrap_or(0);
+    }
+
+    fn get_time_of_creation(&self) -> NaiveTime {
+        return self.groups.iter().map(|group| group.get_earliest_arrival()).min().unwrap_or(NaiveTime::from_hms_opt(0, 0, 0).unwrap());
+    }
+
+    fn get_time_of_deletion(&self) -> NaiveTime {
+        return self.groups.iter().map(|group| group.get_latest_departure()).max().unwrap_or(NaiveTime::from_hms_opt(0, 0, 0).unwrap());
+    }
+
+    fn get_speed(&self) -> Duration {
+        return self.groups.iter().map(|group| group.speed).min().unwrap_or(Duration::zero());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use super::*;
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        return NaiveTime::from_hms_opt(hour, minute, 0).unwrap();
+    }
+
+    #[test]
+    fn the_population_reports_the_bounds_of_the_distributions() {
+        let mut staff = Individual::new(3, time(8, 0), time(17, 0), 0, Duration::seconds(5));
+        staff.set_departure_distribution(TimeDistribution::Uniform { start: time(16, 0), end: time(18, 0) });
+        let mut part_timers = Individual::new(2, time(8, 30), time(12, 30), 0, Duration::seconds(5));
+        part_timers.set_arrival_distribution(TimeDistribution::Uniform { start: time(8, 0), end: time(9, 30) });
+        part_timers.set_departure_distribution(TimeDistribution::Empirical { bins: vec![(time(12, 0), 1.0), (time(13, 0), 0.0)], bin_width: Duration::minutes(30) });
+        let mut population = Population::new(0);
+        population.add_group(staff);
+        population.add_group(part_timers.clone());
+        assert_eq!(population.get_time_of_creation(), time(8, 0));
+        assert_eq!(population.get_time_of_deletion(), time(18, 0));
+        assert_eq!(part_timers.get_time_of_deletion(), time(12, 30));
+
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let (arrival, departure) = part_timers.sample_times(&mut rng);
+            assert!((part_timers.get_earliest_arrival() <= arrival) & (departure <= part_timers.get_latest_departure()));
+        }
+
+        let mut visitors = Individual::new(1, time(10, 0), time(11, 0), 0, Duration::seconds(5));
+        visitors.set_arrival_distribution(TimeDistribution::Normal { mean: time(10, 0), standard_deviation: Duration::minutes(30) });
+        population.add_group(visitors);
+        assert_eq!(population.get_time_of_creation(), NaiveTime::MIN);
+    }
+}
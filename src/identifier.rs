@@ -0,0 +1,367 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+const SENSOR_PREFIX: &str = "Sensor_";
+const SENSOR_NUMBER_SEPARATOR: &str = "_no._";
+const SENSOR_TYPE_SEPARATOR: &str = "_of_type_";
+const MESSAGE_PREFIX: &str = "Message_of_";
+const MOVE_PREFIX: &str = "Movable_object_";
+const MOVE_SEPARATOR: &str = "move_no._";
+
+/// Errors that occur while parsing ids of sensors or events.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseIdError {
+    /// The string is no id of a sensor.
+    NoSensorId(String),
+    /// The string is no id of an uplink message of a sensor.
+    NoMessageId(String),
+    /// The string is no id of a move of a movable object.
+    NoMoveId(String),
+}
+
+impl Display for ParseIdError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            ParseIdError::NoSensorId(id) => write!(f, "{} is no sensor id", id),
+            ParseIdError::NoMessageId(id) => write!(f, "{} is no message id", id),
+            ParseIdError::NoMoveId(id) => write!(f, "{} is no move id", id),
+        };
+    }
+}
+
+impl std::error::Error for ParseIdError {}
+
+/// Parses a number that is written exactly like [usize::to_string] or [i64::to_string] would write it,
+/// so that formatting the parsed number gives back the same string.
+fn parse_number<T: FromStr + ToString>(text: &str) -> Option<T> {
+    let number = text.parse::<T>().ok()?;
+    if number.to_string() != text {
+        return None;
+    }
+    return Some(number);
+}
+
+/// Id of a sensor, of the following pattern: _Sensor_ _ _room-id_ _ no. _ _number_ _ of_type _ _sensor-type-id_,
+/// e.g. _Sensor_RwnD0_no._1_of_type_SensorType_1_.
+/// <br/>The number is the position of the sensor in its room. The id of the room may contain any characters,
+/// also _no._, since the id is parsed from the end.
+/// <br/>Formatting a parsed id gives back the same string and parsing a formatted id gives back the same id.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SensorId {
+    room_id: String,
+    number: usize,
+    sensor_type_id: String,
+}
+
+impl SensorId {
+    pub fn new(room_id: &str, number: usize, sensor_type_id: &str) -> Self {
+        return SensorId {
+            room_id: room_id.to_string(),
+            number,
+            sensor_type_id: sensor_type_id.to_string(),
+        }
+    }
+
+    /// Parses the id of a sensor.
+    /// <br/>Returns an error if `id` is not of the pattern of a sensor id or if the sensor type id contains _ of_type _.
+    pub fn parse(id: &str) -> Result<Self, ParseIdError> {
+        let error = || ParseIdError::NoSensorId(id.to_string());
+        let rest = id.strip_prefix(SENSOR_PREFIX).ok_or_else(error)?;
+        let (rest, sensor_type_id) = rest.rsplit_once(SENSOR_TYPE_SEPARATOR).ok_or_else(error)?;
+        let (room_id, number) = rest.rsplit_once(SENSOR_NUMBER_SEPARATOR).ok_or_else(error)?;
+        let number = parse_number::<usize>(number).ok_or_else(error)?;
+        return Ok(SensorId::new(room_id, number, sensor_type_id));
+    }
+
+    /// Searches the id of a sensor in the id of an event, e.g. in _Message_of_12_Sensor_RwnD0_no._1_of_type_SensorType_1_.
+    /// <br/>Returns the text in front of the sensor id and the sensor id, or None if the event has no sensor id.
+    pub fn find(event_id: &str) -> Option<(String, Self)> {
+        let index = event_id.find(SENSOR_PREFIX)?;
+        let sensor_id = SensorId::parse(&event_id[index..]).ok()?;
+        return Some((event_id[..index].to_string(), sensor_id));
+    }
+
+    /// Gets the id of the room the sensor is in.
+    pub fn get_room_id(&self) -> &str {
+        return self.room_id.as_str();
+    }
+
+    /// Gets the position of the sensor in its room.
+    pub fn get_number(&self) -> usize {
+        return self.number;
+    }
+
+    /// Gets the id of the [SensorType](rubalosim::sensor::SensorType) of the sensor.
+    pub fn get_sensor_type_id(&self) -> &str {
+        return self.sensor_type_id.as_str();
+    }
+}
+
+impl Display for SensorId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}{}{}{}{}{}", SENSOR_PREFIX, self.room_id, SENSOR_NUMBER_SEPARATOR, self.number, SENSOR_TYPE_SEPARATOR, self.sensor_type_id);
+    }
+}
+
+impl FromStr for SensorId {
+    type Err = ParseIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return SensorId::parse(s);
+    }
+}
+
+/// Id of an uplink message of a sensor, of the following pattern: _Message_of_ _ _offspring-number_ _ _sensor-id_,
+/// e.g. _Message_of_12_Sensor_RwnD0_no._1_of_type_SensorType_1_.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MessageId {
+    offspring_number: i64,
+    sensor_id: SensorId,
+}
+
+impl MessageId {
+    pub fn new(offspring_number: i64, sensor_id: SensorId) -> Self {
+        return MessageId {
+            offspring_number,
+            sensor_id,
+        }
+    }
+
+    /// Parses the id of an uplink message.
+    /// <br/>Returns an error if `id` is not of the pattern of a message id.
+    pub fn parse(id: &str) -> Result<Self, ParseIdError> {
+        let error = || ParseIdError::NoMessageId(id.to_string());
+        let rest = id.strip_prefix(MESSAGE_PREFIX).ok_or_else(error)?;
+        let (offspring_number, sensor_id) = rest.split_once("_").ok_or_else(error)?;
+        let offspring_number = parse_number::<i64>(offspring_number).ok_or_else(error)?;
+        let sensor_id = SensorId::parse(sensor_id).map_err(|_| error())?;
+        return Ok(MessageId::new(offspring_number, sensor_id));
+    }
+
+    /// Gets the offspring number of the sensor, see [Sensor::get_number](rubalosim::sensor::Sensor::get_number).
+    pub fn get_offspring_number(&self) -> i64 {
+        return self.offspring_number;
+    }
+
+    pub fn get_sensor_id(&self) -> &SensorId {
+        return &self.sensor_id;
+    }
+}
+
+impl Display for MessageId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}{}_{}", MESSAGE_PREFIX, self.offspring_number, self.sensor_id);
+    }
+}
+
+impl FromStr for MessageId {
+    type Err = ParseIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return MessageId::parse(s);
+    }
+}
+
+/// Id of a move of a movable object, of the following pattern: _Movable_object_ _ _number-of-the-object_ _ _label_ _number-of-the-move_,
+/// e.g. _Movable_object_3_move_no._5_.
+/// <br/>The label is everything between the number of the object and the number of the move, it is kept when parsing an id,
+/// so that formatting the parsed id gives back the same string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MoveId {
+    movable_object: usize,
+    label: String,
+    move_number: usize,
+}
+
+impl MoveId {
+    /// Creates the id of the move with the number `move_number` of the movable object with the number `movable_object`.
+    pub fn new(movable_object: usize, move_number: usize) -> Self {
+        return MoveId {
+            movable_object,
+            label: MOVE_SEPARATOR.to_string(),
+            move_number,
+        }
+    }
+
+    /// Parses the id of a move.
+    /// <br/>Returns an error if `id` is not of the pattern of a move id.
+    pub fn parse(id: &str) -> Result<Self, ParseIdError> {
+        let error = || ParseIdError::NoMoveId(id.to_string());
+        let rest = id.strip_prefix(MOVE_PREFIX).ok_or_else(error)?;
+        let (movable_object, rest) = rest.split_once("_").ok_or_else(error)?;
+        let movable_object = parse_number::<usize>(movable_object).ok_or_else(error)?;
+        let label_length = rest.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+        if label_length == 0 {
+            return Err(error());
+        }
+        let move_number = parse_number::<usize>(&rest[label_length..]).ok_or_else(error)?;
+        return Ok(MoveId {
+            movable_object,
+            label: rest[..label_length].to_string(),
+            move_number,
+        });
+    }
+
+    /// Gets the number of the movable object, which is its index in
+    /// [Simulator::get_matrix_of_nodes_of_movable_objects](rubalosim::simulator::Simulator::get_matrix_of_nodes_of_movable_objects).
+    pub fn get_movable_object(&self) -> usize {
+        return self.movable_object;
+    }
+
+    /// Gets the number of the move, which is the index of the move in the vector of the movable object.
+    pub fn get_move_number(&self) -> usize {
+        return self.move_number;
+    }
+
+    /// Gets the id of another move of the same movable object with the same label.
+    pub fn with_move_number(&self, move_number: usize) -> MoveId {
+        return MoveId {
+            movable_object: self.movable_object,
+            label: self.label.clone(),
+            move_number,
+        }
+    }
+}
+
+impl Display for MoveId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}{}_{}{}", MOVE_PREFIX, self.movable_object, self.label, self.move_number);
+    }
+}
+
+impl FromStr for MoveId {
+    type Err = ParseIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return MoveId::parse(s);
+    }
+}
+
+/// Id of an [Event](rubalosim::simulator::event::Event) of the event list.
+/// <br/>Parsing never fails, ids that are of no known pattern are kept as [EventId::Other].
+/// Formatting a parsed id always gives back the same string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EventId {
+    /// A move of a movable object.
+    Move(MoveId),
+    /// An uplink message of a sensor.
+    Message(MessageId),
+    /// Any other event of a sensor, consisting of a prefix and the id of the sensor.
+    Sensor(String, SensorId),
+    /// Any other event.
+    Other(String),
+}
+
+impl EventId {
+    pub fn parse(id: &str) -> Self {
+        if let Ok(move_id) = MoveId::parse(id) {
+            return EventId::Move(move_id);
+        }
+        if let Ok(message_id) = MessageId::parse(id) {
+            return EventId::Message(message_id);
+        }
+        if let Some((prefix, sensor_id)) = SensorId::find(id) {
+            return EventId::Sensor(prefix, sensor_id);
+        }
+        return EventId::Other(id.to_string());
+    }
+
+    /// Gets the id of the sensor of the event, or None if the event belongs to no sensor.
+    pub fn get_sensor_id(&self) -> Option<&SensorId> {
+        return match self {
+            EventId::Message(message_id) => Some(message_id.get_sensor_id()),
+            EventId::Sensor(_, sensor_id) => Some(sensor_id),
+            _ => None,
+        };
+    }
+}
+
+impl Display for EventId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            EventId::Move(move_id) => write!(f, "{}", move_id),
+            EventId::Message(message_id) => write!(f, "{}", message_id),
+            EventId::Sensor(prefix, sensor_id) => write!(f, "{}{}", prefix, sensor_id),
+            EventId::Other(id) => write!(f, "{}", id),
+        };
+    }
+}
+
+impl From<&str> for EventId {
+    fn from(id: &str) -> Self {
+        return EventId::parse(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // room ids that contain the separators of a sensor id
+    const ROOM_IDS: [&str; 4] = ["RwnD0", "RwnD0_no._3_RwD1_sub", "RwnD0_of_type_RwD1_sub", "F1_RwnD0_of_type_x_no._2_sub"];
+
+    #[test]
+    fn sensor_id_round_trip() {
+        for room_id in ROOM_IDS {
+            let sensor_id = SensorId::new(room_id, 12, "SensorType_1");
+            let text = sensor_id.to_string();
+            assert_eq!(SensorId::parse(text.as_str()), Ok(sensor_id.clone()));
+            assert_eq!(SensorId::parse(text.as_str()).unwrap().to_string(), text);
+            assert_eq!(SensorId::parse(text.as_str()).unwrap().get_room_id(), room_id);
+        }
+    }
+
+    #[test]
+    fn sensor_id_errors() {
+        for id in ["Sensor_RwnD0_no._1", "Sensor_RwnD0_of_type_SensorType_1", "Sensor_RwnD0_no._01_of_type_SensorType_1", "RwnD0_no._1_of_type_SensorType_1"] {
+            assert_eq!(SensorId::parse(id), Err(ParseIdError::NoSensorId(id.to_string())));
+        }
+        // the sensor type id must not contain the type separator
+        let sensor_id = SensorId::new("RwnD0", 1, "Type_of_type_1");
+        assert_ne!(SensorId::parse(sensor_id.to_string().as_str()), Ok(sensor_id));
+    }
+
+    #[test]
+    fn message_id_round_trip() {
+        for room_id in ROOM_IDS {
+            for offspring_number in [0, 7, 123] {
+                let message_id = MessageId::new(offspring_number, SensorId::new(room_id, 1, "SensorType_0"));
+                let text = message_id.to_string();
+                assert_eq!(MessageId::parse(text.as_str()), Ok(message_id.clone()));
+                assert_eq!(MessageId::parse(text.as_str()).unwrap().to_string(), text);
+            }
+        }
+    }
+
+    #[test]
+    fn move_id_round_trip() {
+        let move_id = MoveId::new(3, 5);
+        assert_eq!(move_id.to_string(), "Movable_object_3_move_no._5");
+        assert_eq!(MoveId::parse("Movable_object_3_move_no._5"), Ok(move_id.clone()));
+        assert_eq!(move_id.with_move_number(6).to_string(), "Movable_object_3_move_no._6");
+        // labels of other patterns are kept
+        for text in ["Movable_object_3_move_no._5", "Movable_object_0_no._2_of_type_7", "Movable_object_12_x1_move_no._0"] {
+            assert_eq!(MoveId::parse(text).unwrap().to_string(), text);
+        }
+        for text in ["Movable_object_3_5", "Movable_object_03_move_no._5", "Movable_object_3_move_no._", "Movable_object__move_no._5"] {
+            assert_eq!(MoveId::parse(text), Err(ParseIdError::NoMoveId(text.to_string())));
+        }
+    }
+
+    #[test]
+    fn event_id_round_trip() {
+        let mut texts = vec!["Movable_object_3_move_no._5".to_string(), "Uplink_Message_light:0".to_string(), "Start".to_string()];
+        for room_id in ROOM_IDS {
+            let sensor_id = SensorId::new(room_id, 2, "SensorType_1");
+            texts.push(MessageId::new(4, sensor_id.clone()).to_string());
+            texts.push(format!("Turn_off_{}", sensor_id));
+        }
+        for text in texts {
+            assert_eq!(EventId::parse(text.as_str()).to_string(), text);
+        }
+        let sensor_id = SensorId::new(ROOM_IDS[3], 2, "SensorType_1");
+        assert_eq!(EventId::parse(MessageId::new(4, sensor_id.clone()).to_string().as_str()), EventId::Message(MessageId::new(4, sensor_id.clone())));
+        assert_eq!(EventId::parse(format!("Turn_off_{}", sensor_id).as_str()), EventId::Sensor("Turn_off_".to_string(), sensor_id));
+        assert_eq!(EventId::parse("Start"), EventId::Other("Start".to_string()));
+    }
+}
@@ -4,8 +4,45 @@ pub mod room;
 /// This module is for the representation of humans that are moving through the building.
 pub mod human;
 
+/// This module is for the timing of the movements of humans through the building.
+pub mod movement;
+
 /// This module is for the loading of weather data and changing the event list.
 pub mod weather;
 
 /// This module is for the calculation of energy efficiency.
-pub mod evaluation;
\ No newline at end of file
+pub mod evaluation;
+
+/// This module is for the creation and parsing of the ids of sensors and events.
+pub mod identifier;
+
+/// This module is for the step by step creation of buildings without keeping track of the sensor numbers.
+pub mod builder;
+
+/// This module is for shortest paths and reachability in buildings.
+pub mod path;
+
+/// This module is for the creation of buildings via specification files.
+pub mod specification;
+
+/// This module is for the generation of random office buildings.
+pub mod generator;
+
+/// This module is for saving and loading buildings as JSON.
+pub mod persistence;
+
+/// This module is for the export of buildings as diagrams.
+pub mod export;
+
+/// This module is for the creation of open-plan areas with desks and shared lights.
+pub mod open_plan;
+
+/// This module is for the daily activity schedules of humans.
+pub mod schedule;
+
+/// This module is for calendars with weekdays, weekends and holidays for simulations of several days.
+pub mod calendar;
+
+/// This module is for the shared setup of the tests.
+#[cfg(test)]
+mod testing;
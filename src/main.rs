@@ -3,34 +3,80 @@ use std::ops::Index;
 use std::sync::Arc;
 use rand::{Rng, thread_rng};
 use petgraph::graph::{NodeIndex};
-use building_automation::room::Building;
+use building_automation::room::{Building, DoorSensorKind, RoomType, RulePolicy};
+use building_automation::builder::BuildingBuilder;
+use building_automation::identifier::{MessageId, MoveId, SensorId};
 use rubalosim::simulator::{Simulator};
 use rubalosim::simulator::parameters::Parameters;
 use rubalosim::simulator::event::{Event, Events};
 use rubalosim::rule::Rule;
-use building_automation::human::{Individual};
-use chrono::{Duration, NaiveTime};
+use building_automation::human::{Individual, Population, TimeDistribution};
+use building_automation::schedule::{Meeting, Schedule};
+use chrono::{Duration, Local, NaiveDate, NaiveTime, Timelike};
 use elorapi::rules::{RefValue, Condition, Action};
-use building_automation::evaluation::Evaluation;
+use building_automation::evaluation::{ConsumptionSummary, Evaluation};
+use building_automation::movement::{apply_door_states, apply_population_on_day, create_door_sensor_events, Trajectories};
+use building_automation::calendar::Calendar;
+
+/// Creates a group of individuals with a walking speed, which is only used for connections with a length or between rooms with coordinates.
+fn create_group(name: &str, number_of_movable_objects: u32, time_of_creation: NaiveTime, time_of_deletion: NaiveTime, number_of_random_moves: u32, walking_speed: f64) -> Individual {
+    let mut group = Individual::new(number_of_movable_objects, time_of_creation, time_of_deletion, number_of_random_moves, Duration::seconds(40));
+    group.set_name(name.to_string());
+    group.set_walking_speed(walking_speed);
+    return group;
+}
 
-fn startup() -> Simulator {
-    let individuals = Arc::new(Individual::new(50, NaiveTime::from_hms_opt(8,0,0).unwrap(), NaiveTime::from_hms_opt(18,0,0).unwrap(), 3, Duration::seconds(40)));
+fn startup() -> (Simulator, Arc<Building>, Arc<Population>) {
+    let mut individuals = Population::new(1);
+    let mut staff = create_group("Staff", 32, NaiveTime::from_hms_opt(8,0,0).unwrap(), NaiveTime::from_hms_opt(17,0,0).unwrap(), 3, 1.4);
+    staff.set_arrival_distribution(TimeDistribution::Normal { mean: NaiveTime::from_hms_opt(8,0,0).unwrap(), standard_deviation: Duration::minutes(30) });
+    staff.set_departure_distribution(TimeDistribution::Normal { mean: NaiveTime::from_hms_opt(17,0,0).unwrap(), standard_deviation: Duration::minutes(45) });
+    let mut schedule = Schedule::new();
+    schedule.meetings.push(Meeting { start: NaiveTime::from_hms_opt(10,0,0).unwrap(), duration: Duration::hours(1), attendance: 0.3, room: None });
+    schedule.meetings.push(Meeting { start: NaiveTime::from_hms_opt(14,0,0).unwrap(), duration: Duration::minutes(30), attendance: 0.2, room: None });
+    staff.set_schedule(schedule);
+    // everyone gets an own office as home room, which is chosen out of the destinations by the room affinities
+    for room_type in [RoomType::Kitchen, RoomType::Restroom, RoomType::MeetingRoom] {
+        staff.set_room_affinity(room_type, 0.0);
+    }
+    staff.set_has_home_rooms(true);
+    individuals.add_group(staff);
+    let mut part_timers = create_group("Part-timers", 10, NaiveTime::from_hms_opt(8,30,0).unwrap(), NaiveTime::from_hms_opt(12,30,0).unwrap(), 2, 1.4);
+    part_timers.set_arrival_distribution(TimeDistribution::Uniform { start: NaiveTime::from_hms_opt(8,0,0).unwrap(), end: NaiveTime::from_hms_opt(9,30,0).unwrap() });
+    let mut schedule = Schedule::new();
+    schedule.lunch = None;
+    part_timers.set_schedule(schedule);
+    for room_type in [RoomType::Kitchen, RoomType::Restroom, RoomType::MeetingRoom] {
+        part_timers.set_room_affinity(room_type, 0.0);
+    }
+    part_timers.set_has_home_rooms(true);
+    individuals.add_group(part_timers);
+    let mut visitors = create_group("Visitors", 5, NaiveTime::from_hms_opt(10,0,0).unwrap(), NaiveTime::from_hms_opt(15,0,0).unwrap(), 2, 1.2);
+    visitors.set_room_affinity(RoomType::MeetingRoom, 5.0);
+    visitors.set_room_affinity(RoomType::Office, 0.2);
+    individuals.add_group(visitors);
+    // cleaners visit many rooms, before the offices are locked
+    individuals.add_group(create_group("Cleaners", 3, NaiveTime::from_hms_opt(17,0,0).unwrap(), NaiveTime::from_hms_opt(20,0,0).unwrap(), 10, 1.0));
+    let individuals = Arc::new(individuals);
 
     //let individuals = Arc::new(Individual::new(10, NaiveTime::from_hms_opt(8,0,0).unwrap(), NaiveTime::from_hms_opt(18,0,0).unwrap(), 3, Duration::seconds(40)));
-    let mut parameters = Parameters::new(individuals);
+    let mut parameters = Parameters::new(individuals.clone());
 
     //parameters.add_device_profile_via_file(1203, None, Some("././specification_files/uplink_specification_file_1.json")).unwrap();
     parameters.add_device_profile_via_file(850, Some("././specification_files/downlink_specification_file_2_light.json"), Some("././specification_files/uplink_specification_file_2_light.json")).unwrap();
     parameters.add_device_profile_via_file(250, None, Some("././specification_files/uplink_specification_file_3.json")).unwrap();
+    // door contacts and people counters, their messages are created by create_door_sensor_events
+    parameters.add_device_profile_via_file(250, None, Some("././specification_files/uplink_specification_file_4_contact.json")).unwrap();
+    parameters.add_device_profile_via_file(250, None, Some("././specification_files/uplink_specification_file_5_counter.json")).unwrap();
 
     let building = create_building(parameters.borrow_mut());
 
-    parameters.set_underlying_structure(building);
-    create_rules(parameters.borrow_mut());
+    parameters.set_underlying_structure(building.clone());
+    create_rules(parameters.borrow_mut(), &building);
 
     let simulator = Simulator::new(parameters);
 
-    return simulator;
+    return (simulator, building, individuals);
 }
 
 fn create_building(parameters: &mut Parameters) -> Arc<Building> {
@@ -41,75 +87,98 @@ fn create_building(parameters: &mut Parameters) -> Arc<Building> {
    // let sensor_types_for_rooms = vec![(1 as u32, parameters.get_sensor_types()[0].clone()), (1 as u32, parameters.get_sensor_types()[1].clone())];
 
 
-    let mut building = Building::new();
-    let mut offspring_number:i64 = -1;
+    let mut builder = BuildingBuilder::new();
     for i in 0..12 {
         if i <= 1 {
-            offspring_number= building.add_room_with_doors(i, sensor_types_for_rooms.clone(), false, offspring_number);
+            builder.add_room_with_doors(i, sensor_types_for_rooms.clone(), false).unwrap();
         }
         if i <= 4 {
-            building.add_staircase(i);
+            builder.add_staircase(i).unwrap();
         }
-        offspring_number = building.add_room_without_doors(i, sensor_types_for_rooms.clone(), false, offspring_number);
+        builder.add_room_without_doors(i, sensor_types_for_rooms.clone(), false).unwrap();
     }
     let vec1 = vec![("RwnD0", "RwD0"), ("RwD0", "RwnD1"), ("RwnD1", "S0"), ("RwnD1", "RwD1"), ("RwnD1","RwnD2"), ("RwnD2", "RwnD3"), ("RwnD2", "RwnD4"), ("RwnD4", "S1"), ("RwnD4", "RwnD5"), ("RwnD5", "RwnD0"), ("RwnD5", "S2"), ("RwnD5", "RwnD6"), ("RwnD6", "RwnD7"), ("RwnD7", "S3"), ("RwnD7", "RwnD8"), ("RwnD8", "RwnD4"), ("RwnD8", "RwnD9"), ("RwnD9", "RwnD10"), ("RwnD10", "RwnD7"), ("RwnD10", "S4"), ("RwnD10", "RwnD11"), ("RwnD11", "RwD1")];
-    let mut ok = false;
     let mut i = 0;
     for pair in vec1 {
         if pair.0.contains("RwD") | pair.1.contains("RwD") {
-            ok = building.new_door_connection(pair.0.to_string(), pair.1.to_string(), i);
+            builder.new_door_connection(pair.0.to_string(), pair.1.to_string(), i).unwrap();
         } else {
-            ok = building.new_no_door_connection(pair.0.to_string(), pair.1.to_string(), i);
+            builder.new_no_door_connection(pair.0.to_string(), pair.1.to_string(), i).unwrap();
         }
         i += 1;
     }
-    let mut ok = false;
-
-    (ok, offspring_number) = building.add_sub_rooms_with_doors(14, "RwnD0".to_string(), sensor_types_for_sub_rooms.clone(), true, offspring_number);
 
-    let _ = building.new_door_connection("RwnD0_RwD10_sub".to_string(), "RwnD0_RwD11_sub".to_string(), 0);
-    let _ = building.new_door_connection("RwnD0_RwD10_sub".to_string(), "RwnD0_RwD11_sub".to_string(), 1);
-    (ok, offspring_number) = building.add_sub_rooms_with_doors(7, "RwnD1".to_string(), sensor_types_for_sub_rooms.clone(), true, offspring_number);
-
-    (ok, offspring_number) = building.add_sub_rooms_with_doors(7, "RwnD5".to_string(), sensor_types_for_sub_rooms.clone(), true, offspring_number);
-    (ok, offspring_number) = building.add_sub_rooms_with_doors(3, "RwnD6".to_string(), sensor_types_for_sub_rooms.clone(), true, offspring_number);
-    (ok, offspring_number) = building.add_sub_rooms_with_doors(6, "RwnD7".to_string(), sensor_types_for_sub_rooms.clone(), true, offspring_number);
-    (ok, offspring_number) = building.add_sub_rooms_with_doors(3, "RwnD8".to_string(), sensor_types_for_sub_rooms.clone(), true, offspring_number);
-    (ok, offspring_number) = building.add_sub_rooms_with_doors(5, "RwnD9".to_string(), sensor_types_for_sub_rooms.clone(), true, offspring_number);
-    (ok, offspring_number) = building.add_sub_rooms_with_doors(10, "RwnD10".to_string(), sensor_types_for_sub_rooms.clone(), true, offspring_number);
-    (ok, offspring_number) = building.add_sub_rooms_with_doors(9, "RwnD11".to_string(), sensor_types_for_sub_rooms.clone(), true, offspring_number);
-    (ok, offspring_number) = building.add_sub_rooms_with_doors(5, "RwD0".to_string(), sensor_types_for_sub_rooms.clone(), true, offspring_number);
-    let _ = building.new_door_connection("RwD0_RwD0_sub".to_string(), "RwD0_RwD1_sub".to_string(), 0);
-    let _ = building.new_door_connection("RwD0_RwD2_sub".to_string(), "RwD0_RwD3_sub".to_string(), 1);
-    (ok, offspring_number) = building.add_sub_rooms_with_doors(3, "RwD1".to_string(), sensor_types_for_sub_rooms.clone(), true, offspring_number);
+    builder.add_sub_rooms_with_doors(14, "RwnD0".to_string(), sensor_types_for_sub_rooms.clone(), true).unwrap();
+
+    builder.new_door_connection("RwnD0_RwD10_sub".to_string(), "RwnD0_RwD11_sub".to_string(), 1).unwrap();
+    builder.add_sub_rooms_with_doors(7, "RwnD1".to_string(), sensor_types_for_sub_rooms.clone(), true).unwrap();
+
+    builder.add_sub_rooms_with_doors(7, "RwnD5".to_string(), sensor_types_for_sub_rooms.clone(), true).unwrap();
+    builder.add_sub_rooms_with_doors(3, "RwnD6".to_string(), sensor_types_for_sub_rooms.clone(), true).unwrap();
+    builder.add_sub_rooms_with_doors(6, "RwnD7".to_string(), sensor_types_for_sub_rooms.clone(), true).unwrap();
+    builder.add_sub_rooms_with_doors(3, "RwnD8".to_string(), sensor_types_for_sub_rooms.clone(), true).unwrap();
+    builder.add_sub_rooms_with_doors(5, "RwnD9".to_string(), sensor_types_for_sub_rooms.clone(), true).unwrap();
+    builder.add_sub_rooms_with_doors(10, "RwnD10".to_string(), sensor_types_for_sub_rooms.clone(), true).unwrap();
+    builder.add_sub_rooms_with_doors(9, "RwnD11".to_string(), sensor_types_for_sub_rooms.clone(), true).unwrap();
+    builder.add_sub_rooms_with_doors(5, "RwD0".to_string(), sensor_types_for_sub_rooms.clone(), true).unwrap();
+    builder.new_door_connection("RwD0_RwD0_sub".to_string(), "RwD0_RwD1_sub".to_string(), 0).unwrap();
+    builder.new_door_connection("RwD0_RwD2_sub".to_string(), "RwD0_RwD3_sub".to_string(), 1).unwrap();
+    builder.add_sub_rooms_with_doors(3, "RwD1".to_string(), sensor_types_for_sub_rooms.clone(), true).unwrap();
+    builder.set_room_type("RwnD0_RwD0_sub", RoomType::Kitchen).unwrap();
+    builder.set_room_type("RwnD7_RwD0_sub", RoomType::Kitchen).unwrap();
+    builder.set_room_type("RwnD0_RwD1_sub", RoomType::Restroom).unwrap();
+    builder.set_room_type("RwnD7_RwD1_sub", RoomType::Restroom).unwrap();
+    builder.set_room_type("RwnD5_RwD0_sub", RoomType::MeetingRoom).unwrap();
+    builder.set_room_type("RwnD10_RwD0_sub", RoomType::MeetingRoom).unwrap();
+    // the occupancy of the kitchens and meeting rooms is estimated by people counters at their doors,
+    // the doors of the rooms with doors get contacts
+    let counter = vec![(1 as u32, parameters.get_sensor_types()[3].clone())];
+    for id in ["RwnD0_RwD0_sub", "RwnD7_RwD0_sub", "RwnD5_RwD0_sub", "RwnD10_RwD0_sub"] {
+        let parent_id = builder.get_building().parent(id).unwrap();
+        builder.add_sensors_to_door(id, parent_id.as_str(), DoorSensorKind::PeopleCounter(id.to_string()), counter.clone()).unwrap();
+    }
+    let contact = vec![(1 as u32, parameters.get_sensor_types()[2].clone())];
+    for (id_room_1, id_room_2) in [("RwnD0", "RwD0"), ("RwD0", "RwnD1"), ("RwnD1", "RwD1"), ("RwnD11", "RwD1")] {
+        builder.add_sensors_to_door(id_room_1, id_room_2, DoorSensorKind::Contact, contact.clone()).unwrap();
+    }
+    // the offices are locked after hours
+    builder.lock_sub_rooms(NaiveTime::from_hms_opt(20, 0, 0).unwrap(), NaiveTime::from_hms_opt(6, 0, 0).unwrap());
 
 
+    let building = builder.get_building();
     let count = building.get_number_of_rooms();
     let count_e = building.get_floors().edge_count();
     println!("Number of rooms: {}, Number of edges: {}", count, count_e);
-    println!("Offspring number {}", offspring_number);
+    let report = building.validate();
+    if !report.is_valid() {
+        println!("{}", report);
+    }
+    println!("Number of sensors {}", builder.get_number_of_sensors());
+    //building.write_svg("./building.svg".to_string()).unwrap();
+    //building.write_dot("./building.dot".to_string()).unwrap();
     // let neighbours = building.get_neighbours_ids("RwnD4".to_string());
     //println!("{:?}", neighbours);
 
-    let connections = building.get_connection_ids("RwnD4".to_string());
+    // let connections = building.get_connection_ids("RwnD4".to_string());
     //println!("{:?}", connections);
 
-    let connections = building.get_connection_ids("RwnD0_RwD10_sub".to_string());
+    // let connections = building.get_connection_ids("RwnD0_RwD10_sub".to_string());
     //println!("{:?}", connections);
 
+    let (building, offspring_number) = builder.build();
     parameters.set_number_of_sensors(offspring_number);
-    Arc::new(building)
+    building
 }
 
-/* rules:
+/* rules, depending on the rule policy of the room, which is the one of its type if not set otherwise:
  *       for all rooms: When someone entering a room (so when the occupancy sensor detects a person) then the light should go on
- *       sub room: When someone is leaving a sub-room (when the occupancy sensor does not detect a motion) the lights should go off
- *       not sub room: When someone is leaving the room (when the occupancy sensor does not detect a motion) the light should be:
+ *       SwitchOff (e.g. offices, the sub rooms): When someone is leaving a sub-room (when the occupancy sensor does not detect a motion) the lights should go off
+ *       DimOrSwitchOff (e.g. corridors, the other rooms): When someone is leaving the room (when the occupancy sensor does not detect a motion) the light should be:
             - dimmed: when the time is between 8pm and 5:59:59am
             - turned off: when the time is between 6 am and 7:59:59pm
  *
  */
-fn create_rules(parameters: &mut Parameters) {
+fn create_rules(parameters: &mut Parameters, building: &Building) {
     // lights should go out
     let start_time_condition_for_turning_lights_off = NaiveTime::from_hms_opt(18, 0, 0).unwrap();
     let end_time_condition_for_turning_lights_off = NaiveTime::from_hms_opt(6, 29, 59).unwrap();
@@ -174,7 +243,8 @@ fn create_rules(parameters: &mut Parameters) {
             rules.push(turn_lights_on_when_arriving_rule);
             rule_1 += 1;
             */
-            if node_id.contains(&"_sub".to_string()) {
+            let rule_policy = building.get_rule_policy(node_id.as_str()).unwrap();
+            if rule_policy == RulePolicy::SwitchOff {
                 let mut type_3_sensor_measures_no_occupancy_conditions = Vec::<Condition>::new();
                 for sensor_id_type_3 in &sensors_id_type_3 {
                     type_3_sensor_measures_no_occupancy_conditions.push(Condition::Device(Rule::create_device_condition(sensor_id_type_3.0.clone(), sensor_id_type_3.1.clone(), 0, "==".to_string(), RefValue::String("false".to_string()))));
@@ -227,7 +297,7 @@ fn create_rules(parameters: &mut Parameters) {
                 rules.push(turn_lights_on_when_arriving_rule_sub);
                 rule_5 += 1;
 
-            } else {
+            } else if rule_policy == RulePolicy::DimOrSwitchOff {
                 let mut type_3_sensor_measures_no_occupancy_conditions = Vec::<Condition>::new();
                 for sensor_id_type_3 in &sensors_id_type_3 {
                     type_3_sensor_measures_no_occupancy_conditions.push(Condition::Device(Rule::create_device_condition(sensor_id_type_3.0.clone(), sensor_id_type_3.1.clone(), 0, "==".to_string(), RefValue::String("false".to_string()))));
@@ -308,6 +378,55 @@ fn create_rules(parameters: &mut Parameters) {
             }
         }
     }
+    // the lights of a lighting zone are turned off when none of its desks is occupied and turned on when one of them is
+    for zone in building.get_lighting_zones() {
+        let mut sensors_id_type_3 = Vec::<(String, i64)>::new();
+        for desk_id in zone.get_desks() {
+            let node_index = building.node_index(desk_id.as_str()).unwrap();
+            for sensor in underlying_structure.get_graph_structure().index(node_index).get_sensors() {
+                if sensor.get_sensor_type().get_id().contains("SensorType_1") {
+                    sensors_id_type_3.push((sensor.get_id(), sensor.get_number()));
+                }
+            }
+        }
+        let zone_index = building.node_index(zone.get_id().as_str()).unwrap();
+        let sensors_id_type_2: Vec<(String, i64)> = underlying_structure.get_graph_structure().index(zone_index).get_sensors().iter()
+            .filter(|sensor| sensor.get_sensor_type().get_id().contains("SensorType_0"))
+            .map(|sensor| (sensor.get_id(), sensor.get_number())).collect();
+        if sensors_id_type_3.is_empty() | sensors_id_type_2.is_empty() {
+            continue
+        }
+        let len = sensors_id_type_3.len();
+
+        let mut type_3_sensor_measures_no_occupancy_conditions = Vec::<Condition>::new();
+        for sensor_id_type_3 in &sensors_id_type_3 {
+            type_3_sensor_measures_no_occupancy_conditions.push(Condition::Device(Rule::create_device_condition(sensor_id_type_3.0.clone(), sensor_id_type_3.1.clone(), 0, "==".to_string(), RefValue::String("false".to_string()))));
+        }
+        let mut type_2_sensor_turn_lights_off_actions = Vec::<Action>::new();
+        for sensor_id_type_2 in &sensors_id_type_2 {
+            type_2_sensor_turn_lights_off_actions.push(Rule::create_device_action(sensor_id_type_2.0.clone(), sensor_id_type_2.1.clone(), vec![2]));
+        }
+        let bool_ops = vec!["&".to_string(); len - 1];
+        let turn_lights_off_when_leaving_rule = Rule::create_rule("Turn_lights_off_when_leaving_rule_zone_".to_owned() + zone.get_id().as_str(), type_3_sensor_measures_no_occupancy_conditions, bool_ops, type_2_sensor_turn_lights_off_actions);
+        rules.push(turn_lights_off_when_leaving_rule);
+        rule_2 += 1;
+
+        let mut type_3_sensor_measures_occupancy_conditions_on_sub = Vec::<Condition>::new();
+        for sensor_id_type_3 in &sensors_id_type_3 {
+            type_3_sensor_measures_occupancy_conditions_on_sub.push(Condition::Device(Rule::create_device_condition(sensor_id_type_3.0.clone(), sensor_id_type_3.1.clone(), 0, "==".to_string(), RefValue::String("true".to_string()))));
+        }
+        type_3_sensor_measures_occupancy_conditions_on_sub.push(Condition::Time(Rule::create_time_condition(None, start_time_condition_for_turning_lights_on_sub, end_time_condition_for_turning_lights_on_sub)));
+        let mut type_2_sensor_turn_lights_on_actions = Vec::<Action>::new();
+        for sensor_id_type_2 in &sensors_id_type_2 {
+            type_2_sensor_turn_lights_on_actions.push(Rule::create_device_action(sensor_id_type_2.0.clone(), sensor_id_type_2.1.clone(), vec![0]));
+        }
+        let mut bool_ops = vec!["|".to_string(); len - 1];
+        bool_ops.push("&".to_string());
+        let turn_lights_on_when_arriving_rule_sub = Rule::create_rule("Turn_lights_on_when_arriving_rule_zone_+_time".to_owned() + zone.get_id().as_str(), type_3_sensor_measures_occupancy_conditions_on_sub, bool_ops, type_2_sensor_turn_lights_on_actions);
+        rules.push(turn_lights_on_when_arriving_rule_sub);
+        rule_5 += 1;
+    }
+
     println!("rule_1:{}",rule_1);
     println!("rule_2:{}",rule_2);
     println!("rule_3:{}",rule_3);
@@ -318,8 +437,7 @@ fn create_rules(parameters: &mut Parameters) {
 }
 
 // this works as planed
-fn create_uplink_message(simulator: &mut Simulator) {
-    let matrix = simulator.get_matrix_of_nodes_of_movable_objects();
+fn create_uplink_message(simulator: &mut Simulator, matrix: &Trajectories) {
     let eventlist = simulator.get_event_list().get_event_list_copy();
     let structure = simulator.get_parameters().get_underlying_structure();
     let graph = structure.get_graph_structure();
@@ -333,20 +451,14 @@ fn create_uplink_message(simulator: &mut Simulator) {
         let time = event.get_time();
         match action {
             Events::Move(node_index) => {
-                let mut mov_object_number = event.get_id();
-                let mut number_of_move = mov_object_number.clone();
-                // get number of move
-                number_of_move = number_of_move.replace("_", "");
-                let dot_index = number_of_move.find(".").unwrap();
-                number_of_move.replace_range(0..dot_index+1, "");
-                let move_number = number_of_move.parse::<usize>().unwrap();
+                // get number of move and number of movable object
+                let move_id = match MoveId::parse(event.get_id().as_str()) {
+                    Ok(move_id) => move_id,
+                    Err(_) => continue 'event_list
+                };
+                let move_number = move_id.get_move_number();
                 //println!("{}", move_number);
-
-                // get number of movable object
-                mov_object_number = mov_object_number.trim_start_matches("Movable_object_").to_string();
-                let index_of_ = mov_object_number.find("_");
-                mov_object_number.replace_range(index_of_.unwrap().., "");
-                let movable_object_number = mov_object_number.parse::<usize>().unwrap();
+                let movable_object_number = move_id.get_movable_object();
 
                 // and then its nodes_vector
                 let vecs_of_mov_obj = matrix[movable_object_number].clone();
@@ -361,7 +473,11 @@ fn create_uplink_message(simulator: &mut Simulator) {
                     let sensor_type = sensor.get_sensor_type();
                     //let sensor_id = sensor.get_id();
                     if sensor_type.get_id() == "SensorType_1" {
-                        let id = "Message_of_".to_string() + sensor.get_number().to_string().as_str() + "_" + sensor.get_id().as_str();
+                        let sensor_id = match SensorId::parse(sensor.get_id().as_str()) {
+                            Ok(sensor_id) => sensor_id,
+                            Err(_) => continue
+                        };
+                        let id = MessageId::new(sensor.get_number(), sensor_id).to_string();
                         //time plus 1 sec
                         let range = rng.gen_range(0..1000);
                         let new_time = time + Duration::milliseconds(range);
@@ -383,8 +499,7 @@ fn create_uplink_message(simulator: &mut Simulator) {
                         let mut sec_index = index.clone()+1;
                         let mut future_event = eventlist.get(sec_index).unwrap();
                         while future_event.get_time() <= end {
-                            let sensor_id = future_event.get_id();
-                            if sensor_id.contains("SensorType_1") & (id == sensor_id) {
+                            if future_event.get_id() == id {
                                 let new_action = Events::Message("Uplink_Message_occupancy:true,".to_string());
                                 let new_event = Event::new(future_event.get_id().clone(), future_event.get_time(), new_action);
                                 vec_tuple_of_changed_eventlist.push((sec_index, new_event));
@@ -417,20 +532,47 @@ fn create_uplink_message(simulator: &mut Simulator) {
 
 
 fn main() {
-    let mut simulation = startup();
+    // the results of every day are written into a directory named after the date, their total into the current directory
+    let mut calendar = Calendar::new(NaiveDate::from_ymd_opt(2024, 12, 23).unwrap(), NaiveDate::from_ymd_opt(2024, 12, 29).unwrap());
+    calendar.read_holidays("././specification_files/holidays.txt".to_string()).unwrap();
+    let mut summary = ConsumptionSummary::new();
+    for date in calendar.get_days() {
+        println!("Simulating {} ({:?}, occupancy factor {})", date, calendar.get_day_kind(date), calendar.get_occupancy_factor(date));
+        let path = "./".to_owned() + date.to_string().as_str() + "/";
+        std::fs::create_dir_all(path.as_str()).unwrap();
+        let energy_consumption = simulate_day(&calendar, date, path);
+        summary.add_day(&calendar, date, energy_consumption);
+    }
+    summary.write(&calendar, "./".to_string());
+}
+
+/// Simulates a day of the calendar and writes the results into `path`.
+/// <br/>Returns the energy consumption of the day in Wh.
+fn simulate_day(calendar: &Calendar, date: NaiveDate, path: String) -> f64 {
+    let (mut simulation, building, individuals) = startup();
     let event_list = simulation.get_event_list();
 
     let _ = simulation.start_up_simulation(10);
     simulation.add_standard_values_to_uplink_messages(vec!["0".to_string(), "1".to_string()], vec![vec!["Off".to_string()], vec!["false".to_string()]]);
-    create_uplink_message(simulation.borrow_mut());
+    let trajectories = apply_population_on_day(simulation.borrow_mut(), &building, &individuals, calendar, date);
+    let trajectories = apply_door_states(simulation.borrow_mut(), &building, &individuals, &trajectories);
+    create_uplink_message(simulation.borrow_mut(), &trajectories);
+    create_door_sensor_events(simulation.borrow_mut(), &building, &trajectories);
     simulation.rule_execution();
 
 
     //simulation.print_event_list_sensor("Sensor_RwnD0_no._0_of_type_SensorType_3".to_string());
     //simulation.write_events_downlink_message("./".to_string()).unwrap();
     //simulation.write_events_of_movable_object("./".to_string(), "Movable_object_".to_string()).unwrap();
-    let event_list = simulation.ending_simulation("./".to_string());
-    let mut evaluation = Evaluation::new(vec![10.0, 3.0], vec![vec![406.0, 0.0, 0.0], vec![1740.0, 870.0, 0.0]]);
-    evaluation.calculate_and_write_consumption(event_list, "./".to_string());
-    simulation.write_event_list("./".to_string()).unwrap();
+    let event_list = simulation.ending_simulation(path.clone());
+    let mut evaluation = Evaluation::new(vec![10.0, 3.0], building.clone());
+    let energy_consumption = evaluation.calculate_and_write_consumption(event_list, path.clone());
+    evaluation.calculate_and_write_door_counts(event_list, path.clone());
+    simulation.write_event_list(path.clone()).unwrap();
+
+    // archive the building next to the results, so that the run can be reproduced
+    let now = Local::now();
+    let path = path + "Building_" + now.date_naive().to_string().as_str() + "_" + now.time().hour().to_string().as_str() + "_" + now.time().minute().to_string().as_str() + "_" + now.time().second().to_string().as_str() + ".json";
+    building.write_json(path).unwrap();
+    return energy_consumption;
 }
\ No newline at end of file
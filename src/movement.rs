@@ -0,0 +1,439 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime};
+use petgraph::graph::NodeIndex;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rubalosim::movable_object::MovableObjects;
+use rubalosim::simulator::event::{Event, Events};
+use rubalosim::simulator::Simulator;
+use crate::calendar::Calendar;
+use crate::human::{Individual, Population};
+use crate::identifier::{MessageId, MoveId, SensorId};
+use crate::room::{Building, DoorSensorKind, DoorState};
+
+/// Time in seconds, after which a door is closed again when someone passed it.
+pub const DOOR_CLOSING_TIME: i64 = 5;
+
+/// Trajectories of the movable objects, as given by
+/// [Simulator::get_matrix_of_nodes_of_movable_objects](rubalosim::simulator::Simulator::get_matrix_of_nodes_of_movable_objects):
+/// for every movable object a vector of its moves with the time of the move and the node it moves to.
+pub type Trajectories = Vec<Vec<(usize, NaiveTime, NodeIndex)>>;
+
+/// Changes the times of the moves of the movable objects, so that every move takes the
+/// [movement time](Individual::get_movement_time) of the individual instead of its static speed.
+/// <br/>All following moves of a movable object are shifted by the difference, a move never happens before the previous one
+/// and never past 23:59:59, see [add_within_day].
+/// The move events of the event list of the simulator are changed accordingly and the event list is sorted by time again.
+/// <br/>Returns the changed trajectories, since the ones of the simulator can not be changed.
+pub fn apply_movement_times(simulator: &mut Simulator, building: &Building, individual: &Individual) -> Trajectories {
+    let mut trajectories = simulator.get_matrix_of_nodes_of_movable_objects().to_vec();
+    let graph = building.get_floors();
+    // for every movable object and every move the difference between the new and the old time
+    let mut shifts = Vec::<Vec<Duration>>::new();
+    for trajectory in trajectories.iter_mut() {
+        let mut shifts_of_movable_object = vec![Duration::zero(); trajectory.len()];
+        for i in 1..trajectory.len() {
+            let previous = &trajectory[i - 1];
+            let current = &trajectory[i];
+            let id_room_1 = graph[previous.2].get_data().get_id();
+            let id_room_2 = graph[current.2].get_data().get_id();
+            let movement_time = individual.get_movement_time(building, id_room_1.as_str(), id_room_2.as_str());
+            let mut shift = shifts_of_movable_object[i - 1] + movement_time - individual.get_speed();
+            // the new time must not be before the new time of the previous move
+            let gap = current.1 - previous.1;
+            if gap + shift < shifts_of_movable_object[i - 1] {
+                shift = shifts_of_movable_object[i - 1] - gap;
+            }
+            shifts_of_movable_object[i] = shift;
+        }
+        for i in 0..trajectory.len() {
+            trajectory[i].1 = add_within_day(trajectory[i].1, shifts_of_movable_object[i]);
+        }
+        shifts.push(shifts_of_movable_object);
+    }
+
+    let event_list = simulator.get_event_list_mut().get_event_list_mut();
+    for event in event_list.iter_mut() {
+        if let Events::Move(node_index) = event.get_action() {
+            let move_id = match MoveId::parse(event.get_id().as_str()) {
+                Ok(move_id) => move_id,
+                Err(_) => continue
+            };
+            let shift = shifts.get(move_id.get_movable_object()).and_then(|shifts_of_movable_object| shifts_of_movable_object.get(move_id.get_move_number()));
+            if let Some(shift) = shift {
+                *event = Event::new(event.get_id(), add_within_day(event.get_time(), *shift), Events::Move(node_index));
+            }
+        }
+    }
+    event_list.sort_by_key(|event| event.get_time());
+    return trajectories;
+}
+
+/// Creates the trajectory of a movable object, that enters the building in the room `start` at `time_of_creation`
+/// and then visits the rooms in `visits` one after the other.
+/// <br/>Every visit consists of the id of a room and the time the movable object leaves for it, or, if it arrived at
+/// the previous room later, the time it arrived there. It moves along the [shortest path](Building::shortest_path_at)
+/// that is passable at that time, every move takes the [movement time](Individual::get_movement_time) of the individual.
+/// Rooms that can not be reached are left out.
+/// <br/>The moves never wrap past midnight, moves that would end on the next day happen at 23:59:59 instead, see [add_within_day].
+/// <br/>Returns an empty trajectory if the room `start` does not exist.
+pub fn create_trajectory(building: &Building, individual: &Individual, start: &str, time_of_creation: NaiveTime, visits: &[(String, NaiveTime)]) -> Vec<(usize, NaiveTime, NodeIndex)> {
+    let mut trajectory = Vec::<(usize, NaiveTime, NodeIndex)>::new();
+    let start_index = match building.node_index(start) {
+        Some(start_index) => start_index,
+        None => return trajectory
+    };
+    trajectory.push((0, time_of_creation, start_index));
+    let mut position = start.to_string();
+    let mut time = time_of_creation;
+    for (room_id, departure) in visits {
+        time = time.max(*departure);
+        let path = match building.shortest_path_at(position.as_str(), room_id.as_str(), time) {
+            Ok(Some(path)) => path,
+            _ => continue
+        };
+        for next in &path[1..] {
+            time = add_within_day(time, individual.get_movement_time(building, position.as_str(), next.as_str()));
+            trajectory.push((trajectory.len(), time, building.node_index(next.as_str()).unwrap()));
+            position = next.clone();
+        }
+    }
+    return trajectory;
+}
+
+/// Adds a duration to a time of the day. Times after midnight are clamped to 23:59:59 and times before midnight to 00:00:00,
+/// because the simulation covers a single day and a time that wraps around would be sorted to the other end of the day.
+fn add_within_day(time: NaiveTime, duration: Duration) -> NaiveTime {
+    let (new_time, overflow) = time.overflowing_add_signed(duration);
+    if overflow > 0 {
+        return NaiveTime::from_hms_opt(23, 59, 59).unwrap();
+    }
+    if overflow < 0 {
+        return NaiveTime::MIN;
+    }
+    return new_time;
+}
+
+/// Replaces all move events of the event list of the simulator by the moves of the given trajectories
+/// and sorts the event list by time again.
+fn replace_moves(simulator: &mut Simulator, trajectories: &Trajectories) {
+    let event_list = simulator.get_event_list_mut().get_event_list_mut();
+    event_list.retain(|event| !matches!(event.get_action(), Events::Move(_)) | MoveId::parse(event.get_id().as_str()).is_err());
+    for (movable_object, trajectory) in trajectories.iter().enumerate() {
+        for (move_number, (_, time, node_index)) in trajectory.iter().enumerate() {
+            event_list.push(Event::new(MoveId::new(movable_object, move_number).to_string(), *time, Events::Move(*node_index)));
+        }
+    }
+    event_list.sort_by_key(|event| event.get_time());
+}
+
+/// Replaces the moves of the movable objects of the simulator by the moves of the groups of a [Population].
+/// <br/>Every movable object of a group enters the building at one of the [entrances](Building::get_entrances) and leaves
+/// it via one of the [exits](Building::get_exits), at its own [times of arrival and departure](Individual::sample_times).
+/// In between it visits as many [destinations](Building::get_destinations) as its group has random moves, at equal
+/// intervals. The destinations are chosen by their attractiveness multiplied by the
+/// [room affinity](Individual::get_room_affinity) of the group, see [create_trajectory] for the moves between them.
+/// A movable object with a [home room](Population::get_home_rooms) goes there first and returns there halfway between two moves.
+/// <br/>If the group has a [schedule](Individual::get_schedule), every movable object uses its home room or else one of the
+/// destinations as its desk instead and follows the [visits of the schedule](crate::schedule::Schedule::create_visits).
+/// <br/>The choices only depend on the [seed](Population::get_seed) of the population, the building and the groups.
+/// <br/>Returns the trajectories of all movable objects, numbered as in the population.
+pub fn apply_population(simulator: &mut Simulator, building: &Building, population: &Population) -> Trajectories {
+    let mut rng = StdRng::seed_from_u64(population.get_seed());
+    let trajectories = create_population_trajectories(building, population, 1.0, &mut rng);
+    replace_moves(simulator, &trajectories);
+    return trajectories;
+}
+
+/// Replaces the moves of the movable objects of the simulator by the moves of the groups of a [Population] on a specific day
+/// of a [Calendar], see [apply_population].
+/// <br/>Every movable object comes to the building with the probability of the [occupancy factor](Calendar::get_occupancy_factor)
+/// of the day, the trajectory of a movable object that does not come is empty.
+/// <br/>The choices only depend on the seed of the population, the date, the building and the groups, so that every day is different,
+/// but can be reproduced.
+pub fn apply_population_on_day(simulator: &mut Simulator, building: &Building, population: &Population, calendar: &Calendar, date: NaiveDate) -> Trajectories {
+    let mut rng = StdRng::seed_from_u64(seed_of_day(population.get_seed(), date));
+    let trajectories = create_population_trajectories(building, population, calendar.get_occupancy_factor(date), &mut rng);
+    replace_moves(simulator, &trajectories);
+    return trajectories;
+}
+
+/// Mixes the seed of a population with a date, so that neighbouring seeds and dates do not give the same seed,
+/// as e.g. the sum would do for the seed s on the day d + 1 and the seed s + 1 on the day d.
+fn seed_of_day(seed: u64, date: NaiveDate) -> u64 {
+    return mix(mix(seed) ^ date.num_days_from_ce() as u64);
+}
+
+/// Hashes a number with the finalizer of splitmix64, which is stable across platforms and versions of Rust.
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    return z ^ (z >> 31);
+}
+
+/// Creates the trajectories of all movable objects of a population, of which every one is present with the given probability.
+fn create_population_trajectories(building: &Building, population: &Population, presence: f64, rng: &mut StdRng) -> Trajectories {
+    let entrances = building.get_entrances();
+    let exits = building.get_exits();
+    let home_rooms = population.get_home_rooms(building);
+    let mut trajectories = Trajectories::new();
+    for group in population.get_groups() {
+        let destinations = group.get_destination_weights(building);
+        // None if the group has no destination it likes
+        let destination_distribution = WeightedIndex::new(destinations.iter().map(|(_, weight)| *weight)).ok();
+        let number_of_random_moves = group.get_number_of_random_moves();
+        for _ in 0..group.get_number_of_movable_objects() {
+            if (presence < 1.0) && !rng.gen_bool(presence.max(0.0)) {
+                trajectories.push(Vec::new());
+                continue
+            }
+            let home_room = home_rooms[trajectories.len()].clone();
+            let (time_of_creation, time_of_deletion) = group.sample_times(rng);
+            // no negative intervals, so that no visit is before the arrival or wraps past midnight
+            let interval = (time_of_deletion - time_of_creation).max(Duration::zero()) / (number_of_random_moves as i32 + 1);
+            let entrance = match entrances.choose(rng) {
+                Some(entrance) => entrance.clone(),
+                None => {
+                    trajectories.push(Vec::new());
+                    continue
+                }
+            };
+            let mut visits = Vec::<(String, NaiveTime)>::new();
+            if let Some(schedule) = group.get_schedule() {
+                let desk = match (&home_room, &destination_distribution) {
+                    (Some(home_room), _) => Some(home_room.clone()),
+                    (None, Some(destination_distribution)) => Some(destinations[destination_distribution.sample(rng)].0.clone()),
+                    (None, None) => None
+                };
+                if let Some(desk) = desk {
+                    visits = schedule.create_visits(building, desk.as_str(), time_of_creation, time_of_deletion, rng);
+                }
+            } else {
+                if let Some(home_room) = &home_room {
+                    visits.push((home_room.clone(), time_of_creation));
+                }
+                if let Some(destination_distribution) = &destination_distribution {
+                    for i in 1..=number_of_random_moves {
+                        let destination = destinations[destination_distribution.sample(rng)].0.clone();
+                        let departure = add_within_day(time_of_creation, interval * i as i32);
+                        visits.push((destination, departure));
+                        // back home halfway to the next move
+                        if let Some(home_room) = &home_room {
+                            visits.push((home_room.clone(), add_within_day(departure, interval / 2)));
+                        }
+                    }
+                }
+            }
+            if let Some(exit) = exits.choose(rng) {
+                visits.push((exit.clone(), time_of_deletion));
+            }
+            trajectories.push(create_trajectory(building, group, entrance.as_str(), time_of_creation, &visits));
+        }
+    }
+    return trajectories;
+}
+
+/// Changes the trajectories of the movable objects, so that they do not pass [locked](crate::room::DoorState::Locked) doors.
+/// <br/>Every move is replaced by the moves along the [shortest path](Building::shortest_path_at), that is passable
+/// at the time of the move. If there is no such path, e.g. because the room is locked, the move is left out and the
+/// movable object stays where it is. The first move along a path happens at the time of the original move, every further
+/// move takes the [movement time](Individual::get_movement_time) of the group of the movable object in the population.
+/// Following moves are delayed, if they would happen before the end of such a detour, but never past 23:59:59, see [add_within_day].
+/// <br/>The move events of the event list of the simulator are changed accordingly, the moves of a changed trajectory
+/// are numbered again and the event list is sorted by time again.
+/// <br/>Returns the changed trajectories, since the ones of the simulator can not be changed.
+pub fn apply_door_states(simulator: &mut Simulator, building: &Building, population: &Population, trajectories: &Trajectories) -> Trajectories {
+    let (new_trajectories, original_moves) = avoid_locked_doors(building, population, trajectories);
+
+    let event_list = simulator.get_event_list_mut().get_event_list_mut();
+    let mut new_events = Vec::<Event>::new();
+    event_list.retain(|event| {
+        if !matches!(event.get_action(), Events::Move(_)) {
+            return true;
+        }
+        let move_id = match MoveId::parse(event.get_id().as_str()) {
+            Ok(move_id) => move_id,
+            Err(_) => return true
+        };
+        let original_moves_of_movable_object = match original_moves.get(move_id.get_movable_object()) {
+            Some(Some(original_moves_of_movable_object)) => original_moves_of_movable_object,
+            _ => return true
+        };
+        let new_trajectory = &new_trajectories[move_id.get_movable_object()];
+        for (new_move_number, original_move_number) in original_moves_of_movable_object.iter().enumerate() {
+            if *original_move_number != move_id.get_move_number() {
+                continue
+            }
+            let (_, time, new_node_index) = new_trajectory[new_move_number];
+            new_events.push(Event::new(move_id.with_move_number(new_move_number).to_string(), time, Events::Move(new_node_index)));
+        }
+        return false;
+    });
+    event_list.extend(new_events);
+    event_list.sort_by_key(|event| event.get_time());
+    return new_trajectories;
+}
+
+/// Changes the trajectories, so that they do not pass locked doors, see [apply_door_states].
+/// <br/>Returns the changed trajectories and for every changed movable object the number of the original move of every new move.
+fn avoid_locked_doors(building: &Building, population: &Population, trajectories: &Trajectories) -> (Trajectories, Vec<Option<Vec<usize>>>) {
+    let graph = building.get_floors();
+    let mut new_trajectories = Trajectories::new();
+    // for every changed movable object the number of the original move of every new move
+    let mut original_moves = Vec::<Option<Vec<usize>>>::new();
+    for (movable_object, trajectory) in trajectories.iter().enumerate() {
+        let group = population.get_group_of_movable_object(movable_object);
+        let mut new_trajectory = Vec::<(usize, NaiveTime, NodeIndex)>::new();
+        let mut original_moves_of_movable_object = Vec::<usize>::new();
+        for (move_number, current) in trajectory.iter().enumerate() {
+            let (position, previous_time) = match new_trajectory.last() {
+                None => {
+                    new_trajectory.push(*current);
+                    original_moves_of_movable_object.push(move_number);
+                    continue
+                },
+                Some(previous) => (previous.2, previous.1)
+            };
+            let mut time = current.1.max(previous_time);
+            let id_room_1 = graph[position].get_data().get_id();
+            let id_room_2 = graph[current.2].get_data().get_id();
+            let path = if position == current.2 {
+                Some(vec![id_room_2])
+            } else {
+                // the path has to be passable when the move happens, which may be later than planned
+                building.shortest_path_at(id_room_1.as_str(), id_room_2.as_str(), time).ok().flatten()
+                    .map(|path| path[1..].to_vec())
+            };
+            let mut previous_id = id_room_1;
+            for (hop, id) in path.unwrap_or_default().into_iter().enumerate() {
+                if hop > 0 {
+                    if let Some(group) = group {
+                        time = add_within_day(time, group.get_movement_time(building, previous_id.as_str(), id.as_str()));
+                    }
+                }
+                let node_index = building.find_node_index(id.as_str()).unwrap();
+                new_trajectory.push((new_trajectory.len(), time, node_index));
+                original_moves_of_movable_object.push(move_number);
+                previous_id = id;
+            }
+        }
+        let changed = new_trajectory != *trajectory;
+        new_trajectories.push(new_trajectory);
+        original_moves.push(if changed { Some(original_moves_of_movable_object) } else { None });
+    }
+    return (new_trajectories, original_moves);
+}
+
+/// Adds an uplink message of every [door sensor](Building::add_sensors_to_door) to the event list of the simulator,
+/// whenever a movable object passes the door:
+/// door contacts report _open_ and [DOOR_CLOSING_TIME] seconds later _closed_, unless the door is [open](DoorState::Open)
+/// at that time, people counters report _in_ if the object enters
+/// the counted room and _out_ if it leaves it.
+/// <br/>Moves between rooms that are not connected directly are ignored. A door that is passed just before midnight
+/// is closed at 23:59:59, see [add_within_day].
+/// <br/>The door sensors are not part of any node of the [graph](Building::get_floors), so the simulator does not create
+/// any messages for them itself. Their messages are evaluated by [Evaluation::calculate_and_write_door_counts](crate::evaluation::Evaluation::calculate_and_write_door_counts).
+pub fn create_door_sensor_events(simulator: &mut Simulator, building: &Building, trajectories: &Trajectories) {
+    let event_list = simulator.get_event_list_mut();
+    for event in door_sensor_events(building, trajectories) {
+        event_list.add_event(event);
+    }
+}
+
+/// Creates the events of the door sensors, see [create_door_sensor_events].
+fn door_sensor_events(building: &Building, trajectories: &Trajectories) -> Vec<Event> {
+    let graph = building.get_floors();
+    let mut new_events = Vec::<Event>::new();
+    for trajectory in trajectories {
+        for i in 1..trajectory.len() {
+            let id_room_1 = graph[trajectory[i - 1].2].get_data().get_id();
+            let id_room_2 = graph[trajectory[i].2].get_data().get_id();
+            let time = trajectory[i].1;
+            for (kind, sensor) in building.get_door_sensors(id_room_1.as_str(), id_room_2.as_str()) {
+                let sensor_id = match SensorId::parse(sensor.get_id().as_str()) {
+                    Ok(sensor_id) => sensor_id,
+                    Err(_) => continue
+                };
+                let id = MessageId::new(sensor.get_number(), sensor_id).to_string();
+                match kind {
+                    // an open door is not opened and closed
+                    DoorSensorKind::Contact if building.get_door_state(id_room_1.as_str(), id_room_2.as_str(), time) == Some(DoorState::Open) => {},
+                    DoorSensorKind::Contact => {
+                        new_events.push(Event::new(id.clone(), time, Events::Message("Uplink_Message_contact:open,".to_string())));
+                        new_events.push(Event::new(id, add_within_day(time, Duration::seconds(DOOR_CLOSING_TIME)), Events::Message("Uplink_Message_contact:closed,".to_string())));
+                    },
+                    DoorSensorKind::PeopleCounter(room_id) => {
+                        let direction = if room_id == id_room_2 { "in" } else { "out" };
+                        new_events.push(Event::new(id, time, Events::Message("Uplink_Message_counter:".to_string() + direction + ",")));
+                    },
+                }
+            }
+        }
+    }
+    return new_events;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::BuildingBuilder;
+    use crate::room::DoorPeriod;
+    use crate::testing::sensor_types;
+    use super::*;
+
+    /// Gets the time and message of every event.
+    fn describe(events: &[Event]) -> Vec<(NaiveTime, String)> {
+        return events.iter().map(|event| match event.get_action() {
+            Events::Message(message) => (event.get_time(), message),
+            _ => (event.get_time(), String::new())
+        }).collect();
+    }
+
+    #[test]
+    fn doors_passed_before_midnight_are_closed_on_the_same_day() {
+        let types = sensor_types();
+        let mut builder = BuildingBuilder::new();
+        builder.add_room_with_doors(0, Vec::new(), false).unwrap()
+            .add_room_with_doors(1, Vec::new(), false).unwrap()
+            .new_door_connection("RwD0".to_string(), "RwD1".to_string(), 0).unwrap()
+            .add_sensors_to_door("RwD0", "RwD1", DoorSensorKind::Contact, vec![(1, types[2].clone())]).unwrap();
+        let (building, _) = builder.build();
+        let room_0 = building.find_node_index("RwD0").unwrap();
+        let room_1 = building.find_node_index("RwD1").unwrap();
+        let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        let last_second = NaiveTime::from_hms_opt(23, 59, 59).unwrap();
+        let trajectories = vec![
+            vec![(0, NaiveTime::MIN, room_0), (1, noon, room_1)],
+            vec![(0, NaiveTime::MIN, room_1), (1, last_second, room_0)],
+        ];
+        assert_eq!(describe(&door_sensor_events(&building, &trajectories)), vec![
+            (noon, "Uplink_Message_contact:open,".to_string()),
+            (NaiveTime::from_hms_opt(12, 0, DOOR_CLOSING_TIME as u32).unwrap(), "Uplink_Message_contact:closed,".to_string()),
+            (last_second, "Uplink_Message_contact:open,".to_string()),
+            (last_second, "Uplink_Message_contact:closed,".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn delayed_moves_do_not_pass_doors_locked_in_the_meantime() {
+        let mut builder = BuildingBuilder::new();
+        for id_count in 0..4 {
+            builder.add_room_with_doors(id_count, Vec::new(), false).unwrap();
+        }
+        builder.new_door_connection("RwD0".to_string(), "RwD3".to_string(), 0).unwrap()
+            .new_door_connection("RwD3".to_string(), "RwD1".to_string(), 0).unwrap()
+            .new_door_connection("RwD1".to_string(), "RwD2".to_string(), 0).unwrap()
+            .add_door_period("RwD1", "RwD2", DoorPeriod::new(NaiveTime::from_hms_opt(20, 0, 0).unwrap(), NaiveTime::from_hms_opt(6, 0, 0).unwrap(), DoorState::Locked)).unwrap();
+        let (building, _) = builder.build();
+        let mut population = Population::new(0);
+        population.add_group(Individual::new(1, NaiveTime::MIN, NaiveTime::MIN, 0, Duration::seconds(30)));
+        let room = |id: &str| building.find_node_index(id).unwrap();
+        let time = |hour: u32, minute: u32, second: u32| NaiveTime::from_hms_opt(hour, minute, second).unwrap();
+        // the detour via RwD3 ends at 20:00:10, after the door to RwD2 is locked
+        let trajectories = vec![vec![(0, time(8, 0, 0), room("RwD0")), (1, time(19, 59, 40), room("RwD1")), (2, time(19, 59, 50), room("RwD2"))]];
+        let (new_trajectories, original_moves) = avoid_locked_doors(&building, &population, &trajectories);
+        assert_eq!(new_trajectories, vec![vec![(0, time(8, 0, 0), room("RwD0")), (1, time(19, 59, 40), room("RwD3")), (2, time(20, 0, 10), room("RwD1"))]]);
+        assert_eq!(original_moves, vec![Some(vec![0, 1, 1])]);
+    }
+}
@@ -0,0 +1,103 @@
+use rubalosim::sensor::{Sensor, SensorType};
+use crate::room::{Building, BuildingError, ConnectionType, Coordinates, RoomKind, RoomType};
+
+/// Parameters of an open-plan area, see [Building::add_desk_grid].
+#[derive(Debug, Clone)]
+pub struct DeskGrid {
+    pub rows: u32,
+    pub columns: u32,
+    /// Distance between neighbouring desks in m.
+    pub desk_distance: f64,
+    /// Number of rows and columns of desks, that share a [lighting zone](crate::room::LightingZone).
+    pub zone_rows: u32,
+    pub zone_columns: u32,
+    /// Sensors of every desk, e.g. an occupancy sensor.
+    pub desk_sensors: Vec<(u32, SensorType)>,
+    /// Sensors of every lighting zone, e.g. the luminaire.
+    pub zone_sensors: Vec<(u32, SensorType)>,
+    pub windows: bool,
+}
+
+impl DeskGrid {
+    /// Creates the parameters of a grid of desks with windows, which are 2 m apart and share their lights in blocks of 2 x 2 desks.
+    pub fn new(rows: u32, columns: u32, desk_sensors: Vec<(u32, SensorType)>, zone_sensors: Vec<(u32, SensorType)>) -> Self {
+        return DeskGrid {
+            rows,
+            columns,
+            desk_distance: 2.0,
+            zone_rows: 2,
+            zone_columns: 2,
+            desk_sensors,
+            zone_sensors,
+            windows: true,
+        }
+    }
+}
+
+/// Creates the sensors of the given types, the ids are set by the building.
+fn create_sensors(what_sensor_should_be_create: &[(u32, SensorType)], offspring_number: &mut i64) -> Vec<Sensor> {
+    let mut sensors = Vec::<Sensor>::new();
+    for pair in what_sensor_should_be_create {
+        for _ in 0..pair.0 {
+            *offspring_number += 1;
+            sensors.push(Sensor::new(String::new(), pair.1.clone(), *offspring_number));
+        }
+    }
+    return sensors;
+}
+
+impl Building {
+    /// Adds an open-plan area with a grid of desks to a room, e.g. a hot-desking floor.
+    /// <br/>Every desk is a [sub room](Building::add_sub_rooms_without_doors) of type [RoomType::Desk] with the id
+    /// _parent-id_ _ Desk _ _number_ _ sub_, which is connected with the room without a door and has its own sensors.
+    /// The desks are numbered row by row. If the room has [coordinates](Coordinates), the grid is centered on it.
+    /// <br/>Blocks of neighbouring desks share a [lighting zone](crate::room::LightingZone) with the id _parent-id_ _ Zone _ _number_,
+    /// which is a node in the room with the sensors of the lights.
+    /// <br/>Returns the offspring number of the last created sensor, or an error if the room does not exist
+    /// or if one of the desks or zones already exists. In case of an error nothing is added.
+    pub fn add_desk_grid(&mut self, parent_id: String, grid: &DeskGrid, offspring_number_sensors: i64) -> Result<i64, BuildingError> {
+        let level = self.get_floor_of_room(parent_id.as_str()).ok_or(BuildingError::UnknownRoomId(parent_id.clone()))?;
+        let desk_id = |row: u32, column: u32| parent_id.clone() + "_Desk" + (row * grid.columns + column).to_string().as_str() + "_sub";
+        let zone_rows = grid.zone_rows.max(1);
+        let zone_columns = grid.zone_columns.max(1);
+        let zones_per_row = (grid.columns + zone_columns - 1) / zone_columns;
+        let number_of_zones = (grid.rows + zone_rows - 1) / zone_rows * zones_per_row;
+        let zone_id = |zone: u32| parent_id.clone() + "_Zone" + zone.to_string().as_str();
+        for row in 0..grid.rows {
+            for column in 0..grid.columns {
+                if self.get_floor_of_room(desk_id(row, column).as_str()).is_some() {
+                    return Err(BuildingError::DuplicateRoomId(desk_id(row, column)));
+                }
+            }
+        }
+        for zone in 0..number_of_zones {
+            if self.get_floor_of_room(zone_id(zone).as_str()).is_some() | self.get_lighting_zone(zone_id(zone).as_str()).is_some() {
+                return Err(BuildingError::DuplicateRoomId(zone_id(zone)));
+            }
+        }
+
+        let center = self.get_coordinates(parent_id.as_str());
+        let mut offspring_number = offspring_number_sensors;
+        let mut desks_of_zones = vec![Vec::<String>::new(); number_of_zones as usize];
+        for row in 0..grid.rows {
+            for column in 0..grid.columns {
+                let id = desk_id(row, column);
+                let number = row * grid.columns + column;
+                self.insert_room(id.clone(), RoomKind::RoomWithoutDoors, level, grid.windows, Some(parent_id.clone()), Vec::new())?;
+                self.insert_connection(parent_id.as_str(), id.as_str(), ConnectionType::NoDoor, "NoDoor".to_owned() + number.to_string().as_str() + "_desk", None)?;
+                self.add_sensors_to_room(id.clone(), create_sensors(&grid.desk_sensors, &mut offspring_number))?;
+                self.set_room_type(id.as_str(), RoomType::Desk)?;
+                if let Some(center) = center {
+                    let x = center.x + (column as f64 - (grid.columns as f64 - 1.0) / 2.0) * grid.desk_distance;
+                    let y = center.y + (row as f64 - (grid.rows as f64 - 1.0) / 2.0) * grid.desk_distance;
+                    self.set_coordinates(id.as_str(), Coordinates::new(x, y, center.z))?;
+                }
+                desks_of_zones[(row / zone_rows * zones_per_row + column / zone_columns) as usize].push(id);
+            }
+        }
+        for (zone, desks) in desks_of_zones.into_iter().enumerate() {
+            self.add_lighting_zone(zone_id(zone as u32), parent_id.as_str(), desks, create_sensors(&grid.zone_sensors, &mut offspring_number))?;
+        }
+        return Ok(offspring_number);
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use chrono::NaiveTime;
+use petgraph::algo::{astar, dijkstra};
+use petgraph::graph::{EdgeReference, NodeIndex};
+use petgraph::visit::{EdgeFiltered, EdgeRef};
+use rubalosim::structure::{Edge, EdgeData};
+use crate::room::{Building, BuildingError};
+
+/// Length in m of a connection whose length is neither set nor can be calculated from the coordinates of its rooms.
+/// <br/>In a building without any lengths the distance between two rooms is therefore their number of hops.
+pub const DEFAULT_CONNECTION_LENGTH: f64 = 1.0;
+
+/// Radius around a room, see [Building::rooms_within].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Radius {
+    /// Maximal number of connections between two rooms.
+    Hops(usize),
+    /// Maximal distance between two rooms in m.
+    Meters(f64),
+}
+
+impl Building {
+    /// Gets the length of a connection, or the [default length](DEFAULT_CONNECTION_LENGTH) if it is unknown.
+    fn get_weight(&self, edge: EdgeReference<Edge<Arc<dyn EdgeData>>>) -> f64 {
+        let graph = self.get_floors();
+        let id_room_1 = graph[edge.source()].get_data().get_id();
+        let id_room_2 = graph[edge.target()].get_data().get_id();
+        return self.get_connection_length(id_room_1.as_str(), id_room_2.as_str()).unwrap_or(DEFAULT_CONNECTION_LENGTH);
+    }
+
+    /// Gets the ids of the rooms on the shortest path between two rooms, including both rooms.
+    /// <br/>The length of a connection is its [length](Building::get_connection_length) or, if it is unknown,
+    /// the [default length](DEFAULT_CONNECTION_LENGTH).
+    /// <br/>Returns None if there is no path between the rooms, or an error if one of the rooms does not exist.
+    pub fn shortest_path(&self, from: &str, to: &str) -> Result<Option<Vec<String>>, BuildingError> {
+        let start = self.find_node_index(from)?;
+        let goal = self.find_node_index(to)?;
+        let graph = self.get_floors();
+        let path = astar(graph, start, |node| node == goal, |edge| self.get_weight(edge), |_| 0.0);
+        return Ok(path.map(|(_, nodes)| nodes.iter().map(|node| graph[*node].get_data().get_id()).collect()));
+    }
+
+    /// Gets the ids of the rooms on the [shortest path](Building::shortest_path) between two rooms at a specific time,
+    /// which only uses connections that are [passable](Building::is_passable) at that time, e.g. no locked doors.
+    /// <br/>Returns None if there is no such path between the rooms, or an error if one of the rooms does not exist.
+    pub fn shortest_path_at(&self, from: &str, to: &str, time: NaiveTime) -> Result<Option<Vec<String>>, BuildingError> {
+        let start = self.find_node_index(from)?;
+        let goal = self.find_node_index(to)?;
+        let graph = self.get_floors();
+        let passable = EdgeFiltered::from_fn(graph, |edge: EdgeReference<Edge<Arc<dyn EdgeData>>>| {
+            let id_room_1 = graph[edge.source()].get_data().get_id();
+            let id_room_2 = graph[edge.target()].get_data().get_id();
+            return self.is_passable(id_room_1.as_str(), id_room_2.as_str(), time);
+        });
+        let path = astar(&passable, start, |node| node == goal, |edge| self.get_weight(edge), |_| 0.0);
+        return Ok(path.map(|(_, nodes)| nodes.iter().map(|node| graph[*node].get_data().get_id()).collect()));
+    }
+
+    /// Gets the length in m of the [shortest path](Building::shortest_path) between two rooms.
+    /// <br/>Returns None if there is no path between the rooms, or an error if one of the rooms does not exist.
+    pub fn distance(&self, from: &str, to: &str) -> Result<Option<f64>, BuildingError> {
+        let start = self.find_node_index(from)?;
+        let goal = self.find_node_index(to)?;
+        let path = astar(self.get_floors(), start, |node| node == goal, |edge| self.get_weight(edge), |_| 0.0);
+        return Ok(path.map(|(distance, _)| distance));
+    }
+
+    /// Gets the ids of all rooms, that can be reached from a room within a specific [radius](Radius),
+    /// ordered by their distance. The room itself is not included.
+    /// <br/>Returns an error if the room does not exist.
+    pub fn rooms_within(&self, from: &str, radius: Radius) -> Result<Vec<String>, BuildingError> {
+        let start = self.find_node_index(from)?;
+        let graph = self.get_floors();
+        let mut rooms = Vec::<(f64, NodeIndex)>::new();
+        match radius {
+            Radius::Hops(hops) => {
+                let mut hops_of_nodes = HashMap::<NodeIndex, usize>::new();
+                hops_of_nodes.insert(start, 0);
+                let mut queue = VecDeque::from([start]);
+                while let Some(node) = queue.pop_front() {
+                    let hops_of_node = hops_of_nodes[&node];
+                    if hops_of_node == hops {
+                        continue
+                    }
+                    for neighbour in graph.neighbors(node) {
+                        if !hops_of_nodes.contains_key(&neighbour) {
+                            hops_of_nodes.insert(neighbour, hops_of_node + 1);
+                            rooms.push(((hops_of_node + 1) as f64, neighbour));
+                            queue.push_back(neighbour);
+                        }
+                    }
+                }
+            },
+            Radius::Meters(meters) => {
+                let distances = dijkstra(graph, start, None, |edge| self.get_weight(edge));
+                for (node, distance) in distances {
+                    if (node != start) & (distance <= meters) {
+                        rooms.push((distance, node));
+                    }
+                }
+                rooms.sort_by(|(distance_1, node_1), (distance_2, node_2)| distance_1.total_cmp(distance_2).then(node_1.cmp(node_2)));
+            },
+        }
+        return Ok(rooms.iter().map(|(_, node)| graph[*node].get_data().get_id()).collect());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::room::{Coordinates, DoorPeriod, DoorSchedule, DoorState};
+    use super::*;
+
+    /// Creates the rooms RwnD0 to RwnD3, which are connected as RwnD0-RwnD1, RwnD1-RwnD2, RwnD0-RwnD2 and RwnD0-RwnD3.
+    fn create_building() -> Building {
+        let mut building = Building::new();
+        for id_count in 0..4 {
+            building.add_room_without_doors(id_count, Vec::new(), false, -1).unwrap();
+        }
+        for (id_count_1, id_count_2) in [(0, 1), (1, 2), (0, 2), (0, 3)] {
+            building.new_no_door_connection(format!("RwnD{}", id_count_1), format!("RwnD{}", id_count_2), 0).unwrap();
+        }
+        return building;
+    }
+
+    #[test]
+    fn distance_uses_lengths_before_coordinates() {
+        let mut building = create_building();
+        assert_eq!(building.distance("RwnD0", "RwnD2"), Ok(Some(DEFAULT_CONNECTION_LENGTH)));
+        building.set_coordinates("RwnD0", Coordinates::new(0.0, 0.0, 0.0)).unwrap();
+        building.set_coordinates("RwnD1", Coordinates::new(3.0, 4.0, 0.0)).unwrap();
+        building.set_coordinates("RwnD2", Coordinates::new(6.0, 0.0, 0.0)).unwrap();
+        assert_eq!(building.distance("RwnD0", "RwnD2"), Ok(Some(6.0)));
+        assert_eq!(building.shortest_path("RwnD0", "RwnD2"), Ok(Some(vec!["RwnD0".to_string(), "RwnD2".to_string()])));
+        building.set_connection_length("RwnD0", "RwnD2", 20.0).unwrap();
+        assert_eq!(building.distance("RwnD0", "RwnD2"), Ok(Some(10.0)));
+        assert_eq!(building.shortest_path("RwnD0", "RwnD2"), Ok(Some(vec!["RwnD0".to_string(), "RwnD1".to_string(), "RwnD2".to_string()])));
+    }
+
+    #[test]
+    fn rooms_within_meters_are_ordered_by_distance() {
+        let mut building = create_building();
+        building.set_connection_length("RwnD0", "RwnD1", 5.0).unwrap();
+        building.set_connection_length("RwnD1", "RwnD2", 5.0).unwrap();
+        building.set_connection_length("RwnD0", "RwnD2", 6.0).unwrap();
+        building.set_connection_length("RwnD0", "RwnD3", 0.5).unwrap();
+        assert_eq!(building.rooms_within("RwnD0", Radius::Meters(100.0)), Ok(vec!["RwnD3".to_string(), "RwnD1".to_string(), "RwnD2".to_string()]));
+        assert_eq!(building.rooms_within("RwnD0", Radius::Meters(5.5)), Ok(vec!["RwnD3".to_string(), "RwnD1".to_string()]));
+        assert_eq!(building.rooms_within("RwnD3", Radius::Hops(1)), Ok(vec!["RwnD0".to_string()]));
+    }
+
+    #[test]
+    fn shortest_paths_avoid_locked_doors() {
+        let mut building = Building::new();
+        for id_count in 0..3 {
+            building.add_room_with_doors(id_count, Vec::new(), false, -1).unwrap();
+        }
+        for (id_count_1, id_count_2) in [(0, 1), (1, 2), (0, 2)] {
+            building.new_door_connection(format!("RwD{}", id_count_1), format!("RwD{}", id_count_2), 0).unwrap();
+        }
+        let night = DoorPeriod::new(NaiveTime::from_hms_opt(20, 0, 0).unwrap(), NaiveTime::from_hms_opt(6, 0, 0).unwrap(), DoorState::Locked);
+        let mut schedule = DoorSchedule::new(DoorState::Closed);
+        schedule.add_period(night);
+        building.set_door_schedule("RwD0", "RwD2", schedule.clone()).unwrap();
+        let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        let lock = NaiveTime::from_hms_opt(20, 0, 0).unwrap();
+        assert_eq!(building.shortest_path_at("RwD0", "RwD2", noon), Ok(Some(vec!["RwD0".to_string(), "RwD2".to_string()])));
+        assert_eq!(building.shortest_path_at("RwD0", "RwD2", lock), Ok(Some(vec!["RwD0".to_string(), "RwD1".to_string(), "RwD2".to_string()])));
+        building.set_door_schedule("RwD1", "RwD2", schedule).unwrap();
+        assert_eq!(building.shortest_path_at("RwD0", "RwD2", lock), Ok(None));
+        assert_eq!(building.shortest_path("RwD0", "RwD2"), Ok(Some(vec!["RwD0".to_string(), "RwD2".to_string()])));
+    }
+}
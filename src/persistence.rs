@@ -0,0 +1,387 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use chrono::Duration;
+use petgraph::visit::EdgeRef;
+use serde::{Deserialize, Serialize};
+use rubalosim::sensor::{Sensor, SensorType};
+use crate::room::{Building, BuildingError, ConnectionType, Coordinates, DoorSchedule, DoorSensorKind, RoomKind, RoomProperties, RoomType, RulePolicy};
+
+/// Version of the format, that is written by [Building::to_json].
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Errors that can occur while saving or loading a building.
+#[derive(Debug)]
+pub enum PersistenceError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// The file was written in a version of the format that is not supported.
+    UnsupportedVersion(u32),
+    /// A sensor type id in the file is not one of the given sensor types.
+    UnknownSensorType(String),
+    /// The id of a connection does not belong to any kind of connection.
+    UnknownConnectionType(String),
+    /// The saved building is inconsistent, e.g. because a connection refers to an unknown room.
+    Building(BuildingError),
+}
+
+impl Display for PersistenceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PersistenceError::Io(error) => write!(f, "could not read or write building: {}", error),
+            PersistenceError::Json(error) => write!(f, "could not parse or write JSON: {}", error),
+            PersistenceError::UnsupportedVersion(version) => write!(f, "unsupported format version: {}", version),
+            PersistenceError::UnknownSensorType(id) => write!(f, "unknown sensor type: {}", id),
+            PersistenceError::UnknownConnectionType(id) => write!(f, "unknown kind of connection: {}", id),
+            PersistenceError::Building(error) => write!(f, "invalid building: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for PersistenceError {}
+
+impl From<std::io::Error> for PersistenceError {
+    fn from(error: std::io::Error) -> Self {
+        PersistenceError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for PersistenceError {
+    fn from(error: serde_json::Error) -> Self {
+        PersistenceError::Json(error)
+    }
+}
+
+impl From<BuildingError> for PersistenceError {
+    fn from(error: BuildingError) -> Self {
+        PersistenceError::Building(error)
+    }
+}
+
+/// A saved [Building]. The rooms and connections are stored in the order of the graph,
+/// so that a loaded building has the same node indices as the saved one.
+/// <br/>Only values that were set explicitly are stored. Values that are derived, e.g. the lengths of connections
+/// from the coordinates of the rooms or the default entrances, are derived again from the loaded building.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BuildingRecord {
+    pub version: u32,
+    pub floors: Vec<i32>,
+    pub rooms: Vec<RoomRecord>,
+    pub connections: Vec<ConnectionRecord>,
+    /// Entrances, exits and destinations, if they were set explicitly, otherwise the defaults are used.
+    #[serde(default)]
+    pub entrances: Option<Vec<String>>,
+    #[serde(default)]
+    pub exits: Option<Vec<String>>,
+    #[serde(default)]
+    pub destinations: Option<Vec<(String, u32)>>,
+    #[serde(default)]
+    pub lighting_zones: Vec<LightingZoneRecord>,
+    /// Rule policies of room types, that differ from their default profiles.
+    #[serde(default)]
+    pub rule_policies: Vec<(RoomType, RulePolicy)>,
+}
+
+/// A saved room with its sensors.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RoomRecord {
+    pub id: String,
+    pub kind: RoomKind,
+    pub floor: i32,
+    pub windows: bool,
+    /// Id of the parent room, if the room is a sub room.
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// Type of the room, if it was set explicitly, otherwise it depends on the kind of the room.
+    #[serde(default)]
+    pub room_type: Option<RoomType>,
+    /// Rule policy of the room, if it differs from the one of its type.
+    #[serde(default)]
+    pub rule_policy: Option<RulePolicy>,
+    #[serde(default)]
+    pub properties: Option<RoomProperties>,
+    #[serde(default)]
+    pub coordinates: Option<Coordinates>,
+    #[serde(default)]
+    pub sensors: Vec<SensorRecord>,
+}
+
+/// A saved sensor with its offspring number.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SensorRecord {
+    pub id: String,
+    pub number: i64,
+    pub sensor_type: String,
+}
+
+impl SensorRecord {
+    fn new(sensor: &Sensor) -> Self {
+        return SensorRecord {
+            id: sensor.get_id(),
+            number: sensor.get_number(),
+            sensor_type: sensor.get_sensor_type().get_id(),
+        }
+    }
+
+    /// Creates the sensor with the sensor type with the same id out of `sensor_types`.
+    fn to_sensor(&self, sensor_types: &[SensorType]) -> Result<Sensor, PersistenceError> {
+        let sensor_type = sensor_types.iter().find(|sensor_type| sensor_type.get_id() == self.sensor_type)
+            .ok_or(PersistenceError::UnknownSensorType(self.sensor_type.clone()))?;
+        return Ok(Sensor::new(self.id.clone(), sensor_type.clone(), self.number));
+    }
+}
+
+/// A saved connection between two rooms, e.g. with the id _Door3_sub_.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ConnectionRecord {
+    pub id: String,
+    pub rooms: [String; 2],
+    /// Length in m, if it was set explicitly, otherwise it is the distance between the rooms.
+    #[serde(default)]
+    pub length: Option<f64>,
+    /// Travel time in ms, for connections between floors.
+    #[serde(default)]
+    pub travel_time: Option<i64>,
+    /// Sensors at the door.
+    #[serde(default)]
+    pub sensors: Vec<DoorSensorRecord>,
+    /// Schedule of the door, if it is not closed all day.
+    #[serde(default)]
+    pub schedule: Option<DoorSchedule>,
+}
+
+/// A saved [lighting zone](crate::room::LightingZone) with its desks.
+/// <br/>The node of the zone with its sensors is saved as a [room](RoomRecord).
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LightingZoneRecord {
+    pub id: String,
+    pub desks: Vec<String>,
+}
+
+/// A saved sensor at a door with its kind.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DoorSensorRecord {
+    pub kind: DoorSensorKind,
+    #[serde(flatten)]
+    pub sensor: SensorRecord,
+}
+
+impl Building {
+    /// Gets the [record](BuildingRecord) of the building, which can be saved.
+    pub fn to_record(&self) -> BuildingRecord {
+        let graph = self.get_floors();
+        let mut rooms = Vec::<RoomRecord>::new();
+        for index in graph.node_indices() {
+            let node = &graph[index];
+            let id = node.get_data().get_id();
+            let sensors = node.get_sensors().iter().map(SensorRecord::new).collect();
+            rooms.push(RoomRecord {
+                kind: self.get_room_kind(id.as_str()).unwrap(),
+                floor: self.get_floor_of_room(id.as_str()).unwrap(),
+                windows: self.has_windows(id.as_str()),
+                parent: self.parent(id.as_str()),
+                room_type: self.get_explicit_room_type(id.as_str()),
+                rule_policy: self.get_explicit_rule_policy(id.as_str()),
+                properties: self.get_room_properties(id.as_str()).cloned(),
+                coordinates: self.get_coordinates(id.as_str()),
+                sensors,
+                id,
+            });
+        }
+        let mut connections = Vec::<ConnectionRecord>::new();
+        for edge in graph.edge_references() {
+            let id_room_1 = graph[edge.source()].get_data().get_id();
+            let id_room_2 = graph[edge.target()].get_data().get_id();
+            let sensors = self.get_door_sensors(id_room_1.as_str(), id_room_2.as_str()).into_iter()
+                .map(|(kind, sensor)| DoorSensorRecord { kind, sensor: SensorRecord::new(&sensor) }).collect();
+            connections.push(ConnectionRecord {
+                id: edge.weight().get_data().get_id(),
+                sensors,
+                schedule: self.get_door_schedule(id_room_1.as_str(), id_room_2.as_str()).filter(|schedule| *schedule != DoorSchedule::default()),
+                length: self.get_explicit_connection_length(id_room_1.as_str(), id_room_2.as_str()),
+                travel_time: self.get_travel_time(id_room_1.as_str(), id_room_2.as_str()).map(|travel_time| travel_time.num_milliseconds()),
+                rooms: [id_room_1, id_room_2],
+            });
+        }
+        // in the order of the room types, so that saving the same building always gives the same file
+        let rule_policies = RoomType::get_all().into_iter()
+            .filter_map(|room_type| self.get_rule_policies_of_room_types().get(&room_type).map(|rule_policy| (room_type, *rule_policy)))
+            .collect();
+        return BuildingRecord {
+            version: FORMAT_VERSION,
+            floors: self.get_levels().iter().map(|floor| floor.get_level()).collect(),
+            rooms,
+            connections,
+            entrances: self.get_explicit_entrances().cloned(),
+            exits: self.get_explicit_exits().cloned(),
+            destinations: self.get_explicit_destinations().cloned(),
+            lighting_zones: self.get_lighting_zones().iter().map(|zone| LightingZoneRecord {
+                id: zone.get_id(),
+                desks: zone.get_desks().clone(),
+            }).collect(),
+            rule_policies,
+        };
+    }
+
+    /// Creates a building from a [record](BuildingRecord). The sensors get the sensor type with the same id out of `sensor_types`.
+    /// <br/>Returns the building and the offspring number of the sensor with the highest number,
+    /// or an error if the record is of another version or inconsistent.
+    pub fn from_record(record: &BuildingRecord, sensor_types: &[SensorType]) -> Result<(Building, i64), PersistenceError> {
+        if record.version != FORMAT_VERSION {
+            return Err(PersistenceError::UnsupportedVersion(record.version));
+        }
+        let mut building = Building::new();
+        for level in &record.floors {
+            if *level != 0 {
+                building.add_floor(*level)?;
+            }
+        }
+        let mut offspring_number: i64 = -1;
+        for room in &record.rooms {
+            let mut sensors = Vec::<Sensor>::new();
+            for sensor in &room.sensors {
+                sensors.push(sensor.to_sensor(sensor_types)?);
+                offspring_number = offspring_number.max(sensor.number);
+            }
+            building.insert_room(room.id.clone(), room.kind, room.floor, room.windows, room.parent.clone(), sensors)?;
+            if let Some(properties) = &room.properties {
+                building.set_room_properties(room.id.as_str(), properties.clone())?;
+            }
+            if let Some(room_type) = room.room_type {
+                building.set_room_type(room.id.as_str(), room_type)?;
+            }
+            if let Some(rule_policy) = room.rule_policy {
+                building.set_rule_policy(room.id.as_str(), rule_policy)?;
+            }
+            if let Some(coordinates) = room.coordinates {
+                building.set_coordinates(room.id.as_str(), coordinates)?;
+            }
+        }
+        building.update_children();
+        for connection in &record.connections {
+            let connection_type = ConnectionType::from_edge_id(connection.id.as_str())
+                .ok_or(PersistenceError::UnknownConnectionType(connection.id.clone()))?;
+            let [id_room_1, id_room_2] = &connection.rooms;
+            let travel_time = connection.travel_time.map(Duration::milliseconds);
+            building.insert_connection(id_room_1.as_str(), id_room_2.as_str(), connection_type, connection.id.clone(), travel_time)?;
+            if let Some(length) = connection.length {
+                building.set_connection_length(id_room_1.as_str(), id_room_2.as_str(), length)?;
+            }
+            for door_sensor in &connection.sensors {
+                let sensor = door_sensor.sensor.to_sensor(sensor_types)?;
+                building.add_sensors_to_door(id_room_1.as_str(), id_room_2.as_str(), door_sensor.kind.clone(), vec![sensor])?;
+                offspring_number = offspring_number.max(door_sensor.sensor.number);
+            }
+            if let Some(schedule) = &connection.schedule {
+                building.set_door_schedule(id_room_1.as_str(), id_room_2.as_str(), schedule.clone())?;
+            }
+        }
+        for zone in &record.lighting_zones {
+            building.insert_lighting_zone(zone.id.clone(), zone.desks.clone())?;
+        }
+        for (room_type, rule_policy) in &record.rule_policies {
+            building.set_rule_policy_of_room_type(*room_type, *rule_policy);
+        }
+        if let Some(entrances) = &record.entrances {
+            building.set_entrances(entrances.clone())?;
+        }
+        if let Some(exits) = &record.exits {
+            building.set_exits(exits.clone())?;
+        }
+        if let Some(destinations) = &record.destinations {
+            building.set_destinations(destinations.clone())?;
+        }
+        return Ok((building, offspring_number));
+    }
+
+    /// Exports the building as JSON, see [BuildingRecord].
+    pub fn to_json(&self) -> Result<String, PersistenceError> {
+        return Ok(serde_json::to_string_pretty(&self.to_record())?);
+    }
+
+    /// Writes the [JSON export](Building::to_json) of the building to the file at `path`,
+    /// e.g. next to the results of a simulation, so that it can be reproduced.
+    pub fn write_json(&self, path: String) -> Result<(), PersistenceError> {
+        fs::write(path, self.to_json()?)?;
+        return Ok(());
+    }
+
+    /// Creates a building from its [JSON export](Building::to_json), see [Building::from_record].
+    pub fn from_json(json: &str, sensor_types: &[SensorType]) -> Result<(Building, i64), PersistenceError> {
+        let record: BuildingRecord = serde_json::from_str(json)?;
+        return Building::from_record(&record, sensor_types);
+    }
+
+    /// Reads a building from a file written by [Building::write_json], see [Building::from_record].
+    pub fn read_json(path: String, sensor_types: &[SensorType]) -> Result<(Building, i64), PersistenceError> {
+        let json = fs::read_to_string(path)?;
+        return Building::from_json(json.as_str(), sensor_types);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use chrono::NaiveTime;
+    use crate::builder::BuildingBuilder;
+    use crate::open_plan::DeskGrid;
+    use crate::room::{DoorPeriod, DoorState, Orientation};
+    use crate::testing::sensor_types;
+    use super::*;
+
+    /// Creates a building with two floors, which are connected via their staircases, sub rooms, door sensors, a locked door
+    /// and desks with lighting zones on the ground floor.
+    fn create_building(types: &[SensorType]) -> (Arc<Building>, i64) {
+        let mut builder = BuildingBuilder::new();
+        builder.add_floor(1).unwrap();
+        builder.add_room_without_doors(0, vec![(1, types[0].clone()), (2, types[1].clone())], false).unwrap()
+            .add_room_with_doors(1, vec![(1, types[0].clone())], true).unwrap()
+            .add_staircase(0).unwrap()
+            .new_no_door_connection("RwnD0".to_string(), "RwD1".to_string(), 0).unwrap()
+            .new_door_connection("RwnD0".to_string(), "S0".to_string(), 1).unwrap()
+            .add_sub_rooms_with_doors(2, "RwnD0".to_string(), vec![(1, types[1].clone())], true).unwrap()
+            .set_connection_length("RwnD0", "RwD1", 12.5).unwrap()
+            .set_coordinates("RwD1", Coordinates::new(12.5, 0.0, 0.0)).unwrap()
+            .set_room_properties("RwD1", RoomProperties::new(20.0, 3.0).with_windows(4.0, Orientation::South)).unwrap()
+            .set_room_type("RwD1", RoomType::Kitchen).unwrap()
+            .set_entrances(vec!["RwnD0".to_string()]).unwrap()
+            .add_sensors_to_door("RwnD0", "S0", DoorSensorKind::Contact, vec![(1, types[2].clone())]).unwrap()
+            .add_sensors_to_door("RwnD0", "S0", DoorSensorKind::PeopleCounter("S0".to_string()), vec![(1, types[3].clone())]).unwrap()
+            .add_door_period("RwnD0", "S0", DoorPeriod::new(NaiveTime::from_hms_opt(20, 0, 0).unwrap(), NaiveTime::from_hms_opt(6, 0, 0).unwrap(), DoorState::Locked)).unwrap()
+            .add_desk_grid("RwD1".to_string(), &DeskGrid::new(2, 2, vec![(1, types[1].clone())], vec![(1, types[0].clone())])).unwrap();
+        builder.set_current_floor(1).unwrap()
+            .add_staircase(0).unwrap()
+            .add_room_with_doors(0, vec![(1, types[0].clone())], true).unwrap()
+            .new_door_connection("F1_S0".to_string(), "F1_RwD0".to_string(), 0).unwrap()
+            .set_current_floor(0).unwrap()
+            .connect_staircases(0, 0, 1, Duration::seconds(20)).unwrap();
+        return builder.build();
+    }
+
+    /// Gets the ids and numbers of the sensors of all rooms in graph order.
+    fn sensor_numbers(building: &Building) -> Vec<(String, i64)> {
+        let graph = building.get_floors();
+        return graph.node_indices().flat_map(|node| graph[node].get_sensors().iter().map(|sensor| (sensor.get_id(), sensor.get_number())).collect::<Vec<_>>()).collect();
+    }
+
+    #[test]
+    fn saving_and_loading_keeps_the_building() {
+        let types = sensor_types();
+        let (building, offspring_number) = create_building(&types);
+        let json = building.to_json().unwrap();
+        let (loaded, loaded_offspring_number) = Building::from_json(json.as_str(), &types).unwrap();
+        assert_eq!(loaded.to_json().unwrap(), json);
+        assert_eq!(loaded_offspring_number, offspring_number);
+        assert_eq!(sensor_numbers(&loaded), sensor_numbers(&building));
+        let door_sensor_numbers = |building: &Building| building.get_door_sensors("RwnD0", "S0").iter()
+            .map(|(_, sensor)| (sensor.get_id(), sensor.get_number())).collect::<Vec<_>>();
+        assert_eq!(door_sensor_numbers(&loaded).len(), 2);
+        assert_eq!(door_sensor_numbers(&loaded), door_sensor_numbers(&building));
+        assert_eq!(loaded.get_connection_length("RwnD0", "RwD1"), Some(12.5));
+        assert_eq!(loaded.get_travel_time("S0", "F1_S0"), Some(Duration::seconds(20)));
+        assert_eq!(loaded.get_door_schedule("RwnD0", "S0"), building.get_door_schedule("RwnD0", "S0"));
+        assert_ne!(loaded.get_door_schedule("RwnD0", "S0"), Some(DoorSchedule::default()));
+        let zones = |building: &Building| building.get_lighting_zones().iter().map(|zone| (zone.get_id(), zone.get_desks().clone())).collect::<Vec<_>>();
+        assert!(!zones(&loaded).is_empty());
+        assert_eq!(zones(&loaded), zones(&building));
+    }
+}
@@ -1,35 +1,496 @@
 use std::borrow::{Borrow};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 use std::sync::Arc;
+use chrono::{Duration, NaiveTime};
 use petgraph::graph::{Graph, NodeIndex};
 use petgraph::Undirected;
+use serde::{Deserialize, Serialize};
 use rubalosim::sensor::{Sensor, SensorType};
 use rubalosim::structure::{EdgeData, Edge, Node, NodeData, UnderlyingStructure};
+use crate::identifier::SensorId;
 
 
 // source: https://docs.rs/petgraph/latest/petgraph/graph/struct.Graph.html
 // https://github.com/petgraph/petgraph
 
 
+/// Errors that can occur while creating or changing a [Building].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildingError {
+    /// No room with this id exists in the building.
+    UnknownRoomId(String),
+    /// A room with this id already exists in the building.
+    DuplicateRoomId(String),
+    /// The two rooms with these ids are already connected.
+    DuplicateConnection(String, String),
+    /// A room with this id should be connected with itself.
+    SelfLoop(String),
+    /// The room with this id would become its own parent or ancestor.
+    CyclicHierarchy(String),
+    /// The two rooms with these ids are not connected.
+    UnknownConnection(String, String),
+    /// The connection between the rooms with these ids has no door.
+    NoDoorConnection(String, String),
+    /// No floor with this level exists in the building.
+    UnknownFloor(i32),
+    /// A floor with this level already exists in the building.
+    DuplicateFloor(i32),
+    /// The floors with these levels are not adjacent.
+    FloorsNotAdjacent(i32, i32),
+    /// The room with this id should get an attractiveness higher than [MAX_ATTRACTIVENESS].
+    AttractivenessTooHigh(String, u32),
+    /// The room with this id should get windows, but the window area of its properties is 0.
+    NoWindowArea(String),
+    /// The connection between the rooms with these ids should get a length that is negative, infinite or NaN.
+    InvalidLength(String, String),
+    /// The room with this id should get coordinates that are infinite or NaN.
+    InvalidCoordinates(String),
+}
+
+impl Display for BuildingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildingError::UnknownRoomId(id) => write!(f, "unknown room id: {}", id),
+            BuildingError::DuplicateRoomId(id) => write!(f, "duplicate room id: {}", id),
+            BuildingError::DuplicateConnection(id_1, id_2) => write!(f, "{} and {} are already connected", id_1, id_2),
+            BuildingError::SelfLoop(id) => write!(f, "{} can not be connected with itself", id),
+            BuildingError::CyclicHierarchy(id) => write!(f, "{} can not be its own parent or ancestor", id),
+            BuildingError::UnknownConnection(id_1, id_2) => write!(f, "{} and {} are not connected", id_1, id_2),
+            BuildingError::NoDoorConnection(id_1, id_2) => write!(f, "{} and {} are not connected via a door", id_1, id_2),
+            BuildingError::UnknownFloor(level) => write!(f, "unknown floor: {}", level),
+            BuildingError::DuplicateFloor(level) => write!(f, "duplicate floor: {}", level),
+            BuildingError::FloorsNotAdjacent(level_1, level_2) => write!(f, "floor {} and floor {} are not adjacent", level_1, level_2),
+            BuildingError::AttractivenessTooHigh(id, attractiveness) => write!(f, "attractiveness {} of {} is higher than {}", attractiveness, id, MAX_ATTRACTIVENESS),
+            BuildingError::NoWindowArea(id) => write!(f, "{} has no window area, so it can not have windows", id),
+            BuildingError::InvalidLength(id_1, id_2) => write!(f, "the connection between {} and {} can only have a finite, non-negative length", id_1, id_2),
+            BuildingError::InvalidCoordinates(id) => write!(f, "{} can only have finite coordinates", id),
+        }
+    }
+}
+
+impl std::error::Error for BuildingError {}
+
+/// Highest attractiveness of a destination, see [Building::set_destinations].
+/// <br/>[UnderlyingStructure::get_nodes_to_move_to] can only return a plain list of nodes,
+/// so every destination is contained in it as often as its attractiveness.
+pub const MAX_ATTRACTIVENESS: u32 = 100;
+
+
 /// Struct to represent a building. Different rooms and doors are represented as a graph,
 /// where rooms are vertices and doors are edges.
+/// <br/>Every room belongs to a [Floor]. Staircases and elevators on adjacent floors
+/// are connected with each other, so the graph spans the whole building.
 pub struct Building  {
     //Arc is necessary because Graph needs the size of its parameters
-    floors: Graph<Node<Arc<dyn NodeData>>, Edge<Arc<dyn EdgeData>>, Undirected>
+    floors: Graph<Node<Arc<dyn NodeData>>, Edge<Arc<dyn EdgeData>>, Undirected>,
+    // index of the node of every room, by room id
+    room_indices: HashMap<String, NodeIndex>,
+    levels: Vec<Floor>,
+    current_floor: i32,
+    // floor and kind of every room, by room id
+    room_infos: HashMap<String, RoomInfo>,
+    // travel times of the connections between floors, by the ids of the connected rooms
+    travel_times: HashMap<(String, String), Duration>,
+    // lengths of connections in m, that were set explicitly
+    connection_lengths: HashMap<(String, String), f64>,
+    // sensors at the doors between two rooms
+    door_sensors: HashMap<(String, String), Vec<(DoorSensorKind, Sensor)>>,
+    // schedules of the doors, that are not closed all day
+    door_schedules: HashMap<(String, String), DoorSchedule>,
+    // groups of desks that share their lights
+    lighting_zones: Vec<LightingZone>,
+    // rule policies of room types, that differ from their default profiles
+    rule_policies: HashMap<RoomType, RulePolicy>,
+    // explicitly set entrances, exits and destinations with their attractiveness, None if the defaults are used
+    entrances: Option<Vec<String>>,
+    exits: Option<Vec<String>>,
+    destinations: Option<Vec<(String, u32)>>,
 }
 
 impl Building {
     /// Creates a new Building represented as an undirected graph.
+    /// <br/>The building has a ground floor with level 0, which is the current floor.
     pub fn new() -> Building {
         Building {
             floors: Graph::<Node<Arc<dyn NodeData>>, Edge<Arc<dyn EdgeData>>, Undirected>::new_undirected(),
+            room_indices: HashMap::new(),
+            levels: vec![Floor::new(0)],
+            current_floor: 0,
+            room_infos: HashMap::new(),
+            travel_times: HashMap::new(),
+            connection_lengths: HashMap::new(),
+            door_sensors: HashMap::new(),
+            door_schedules: HashMap::new(),
+            lighting_zones: Vec::new(),
+            rule_policies: HashMap::new(),
+            entrances: None,
+            exits: None,
+            destinations: None,
+        }
+    }
+
+    /// Adds a [Floor] with the given level to the building.
+    /// <br/>Returns an error if the floor already exists.
+    pub fn add_floor(&mut self, level: i32) -> Result<(), BuildingError> {
+        if self.levels.iter().any(|floor| floor.get_level() == level) {
+            return Err(BuildingError::DuplicateFloor(level));
+        }
+        self.levels.push(Floor::new(level));
+        self.levels.sort_by_key(|floor| floor.get_level());
+        return Ok(());
+    }
+
+    /// Sets the floor to which all rooms, staircases and elevators are added from now on.
+    /// <br/>Returns an error if the floor does not exist.
+    /// <br/>Rooms that are not on the ground floor get the id of the floor as prefix, e.g. _F1_RwD0_.
+    /// Sub rooms always belong to the floor of their parent.
+    pub fn set_current_floor(&mut self, level: i32) -> Result<(), BuildingError> {
+        if !self.levels.iter().any(|floor| floor.get_level() == level) {
+            return Err(BuildingError::UnknownFloor(level));
+        }
+        self.current_floor = level;
+        return Ok(());
+    }
+
+    /// Gets the level of the floor to which rooms are currently added.
+    pub fn get_current_floor(&self) -> i32 {
+        return self.current_floor;
+    }
+
+    /// Gets all floors of the building, sorted by their level.
+    pub fn get_levels(&self) -> &Vec<Floor> {
+        return self.levels.borrow();
+    }
+
+    /// Gets the level of the floor of a specific room.
+    /// <br/>Returns None if the room could not be found.
+    pub fn get_floor_of_room(&self, id: &str) -> Option<i32> {
+        return self.room_infos.get(id).map(|info| info.floor);
+    }
+
+    /// Gets the [kind](RoomKind) of a specific room.
+    /// <br/>Returns None if the room could not be found.
+    pub fn get_room_kind(&self, id: &str) -> Option<RoomKind> {
+        return self.room_infos.get(id).map(|info| info.kind);
+    }
+
+    /// Returns true if a specific room has windows, false if it has none or if the room could not be found.
+    pub fn has_windows(&self, id: &str) -> bool {
+        return self.room_infos.get(id).map(|info| info.windows).unwrap_or(false);
+    }
+
+    /// Sets the [properties](RoomProperties) of a specific room, e.g. its floor area.
+    /// <br/>Whether the room [has windows](Building::has_windows) is derived from the window area of the properties.
+    /// <br/>Returns an error if the room does not exist.
+    pub fn set_room_properties(&mut self, id: &str, properties: RoomProperties) -> Result<(), BuildingError> {
+        let index = self.find_node_index(id)?;
+        let windows = properties.window_area > 0.0;
+        self.room_infos.get_mut(id).unwrap().properties = Some(properties);
+        self.replace_windows(index, id, windows);
+        return Ok(());
+    }
+
+    /// Sets the [type](RoomType) of a specific room.
+    /// <br/>Returns an error if the room does not exist.
+    pub fn set_room_type(&mut self, id: &str, room_type: RoomType) -> Result<(), BuildingError> {
+        self.find_node_index(id)?;
+        self.room_infos.get_mut(id).unwrap().room_type = Some(room_type);
+        return Ok(());
+    }
+
+    /// Gets the [type](RoomType) of a specific room.
+    /// <br/>If no type was set, the type depends on the kind of the room: staircases and elevators are [RoomType::Staircase],
+    /// lighting zones have the type of their first desk, other sub rooms are [RoomType::Office] and all other rooms are [RoomType::Corridor].
+    /// Returns None if the room could not be found.
+    pub fn get_room_type(&self, id: &str) -> Option<RoomType> {
+        let info = self.room_infos.get(id)?;
+        if let Some(room_type) = info.room_type {
+            return Some(room_type);
+        }
+        if let Some(desk_id) = self.get_lighting_zone(id).and_then(|zone| zone.desks.first()) {
+            return self.get_room_type(desk_id.as_str());
+        }
+        if (info.kind == RoomKind::Staircase) | (info.kind == RoomKind::Elevator) {
+            return Some(RoomType::Staircase);
+        }
+        if info.parent.is_some() {
+            return Some(RoomType::Office);
+        }
+        return Some(RoomType::Corridor);
+    }
+
+    /// Gets the type of a specific room, if it was set via [Building::set_room_type].
+    pub(crate) fn get_explicit_room_type(&self, id: &str) -> Option<RoomType> {
+        return self.room_infos.get(id)?.room_type;
+    }
+
+    /// Sets the [policy](RulePolicy) for the rules of the lights of a specific room,
+    /// instead of the policy of its [type](Building::get_room_type).
+    /// <br/>Returns an error if the room does not exist.
+    pub fn set_rule_policy(&mut self, id: &str, rule_policy: RulePolicy) -> Result<(), BuildingError> {
+        self.find_node_index(id)?;
+        self.room_infos.get_mut(id).unwrap().rule_policy = Some(rule_policy);
+        return Ok(());
+    }
+
+    /// Sets the [policy](RulePolicy) for the rules of the lights of all rooms of a type,
+    /// instead of the policy of the [default profile](RoomType::get_default_profile) of the type.
+    pub fn set_rule_policy_of_room_type(&mut self, room_type: RoomType, rule_policy: RulePolicy) {
+        self.rule_policies.insert(room_type, rule_policy);
+    }
+
+    /// Gets the [policy](RulePolicy) for the rules of the lights of a specific room.
+    /// <br/>This is the policy that was set for the room, otherwise the policy of its [type](Building::get_rule_policy_of_room_type).
+    /// Returns None if the room could not be found.
+    pub fn get_rule_policy(&self, id: &str) -> Option<RulePolicy> {
+        let info = self.room_infos.get(id)?;
+        if let Some(rule_policy) = info.rule_policy {
+            return Some(rule_policy);
+        }
+        return Some(self.get_rule_policy_of_room_type(self.get_room_type(id)?));
+    }
+
+    /// Gets the policy of a specific room, if it was set via [Building::set_rule_policy].
+    pub(crate) fn get_explicit_rule_policy(&self, id: &str) -> Option<RulePolicy> {
+        return self.room_infos.get(id)?.rule_policy;
+    }
+
+    /// Gets the [policy](RulePolicy) for the rules of the lights of the rooms of a type.
+    /// <br/>This is the policy that was set for the type, otherwise the policy of its [default profile](RoomType::get_default_profile).
+    pub fn get_rule_policy_of_room_type(&self, room_type: RoomType) -> RulePolicy {
+        return match self.rule_policies.get(&room_type) {
+            Some(rule_policy) => *rule_policy,
+            None => room_type.get_default_profile().rule_policy,
+        };
+    }
+
+    /// Gets the room types whose policy was set via [Building::set_rule_policy_of_room_type].
+    pub fn get_rule_policies_of_room_types(&self) -> &HashMap<RoomType, RulePolicy> {
+        return &self.rule_policies;
+    }
+
+    /// Gets the [properties](RoomProperties) of a specific room.
+    /// <br/>Returns None if the room could not be found or if no properties were set.
+    pub fn get_room_properties(&self, id: &str) -> Option<&RoomProperties> {
+        return self.room_infos.get(id)?.properties.as_ref();
+    }
+
+    /// Gets the [properties](RoomProperties) of the room of a specific node of the [graph](Building::get_floors).
+    /// <br/>Returns None if the node could not be found or if no properties were set.
+    pub fn get_room_properties_of_node(&self, index: NodeIndex) -> Option<&RoomProperties> {
+        let node = self.floors.node_weight(index)?;
+        return self.get_room_properties(node.get_data().get_id().as_str());
+    }
+
+    /// Gets the ids of all rooms on a specific floor, including staircases, elevators and sub rooms.
+    pub fn get_rooms_on_floor(&self, level: i32) -> Vec<String> {
+        let mut ids = Vec::<String>::new();
+        for index in self.floors.node_indices() {
+            let id = self.floors[index].get_data().get_id();
+            if self.room_infos.get(&id).map(|info| info.floor) == Some(level) {
+                ids.push(id);
+            }
+        }
+        return ids;
+    }
+
+    /// Gets the time it takes to travel between two rooms on different floors, e.g. between the
+    /// staircase _S0_ on the ground floor and the staircase _F1_S0_ on the first floor.
+    /// <br/>Returns None if the rooms are not connected via [Building::connect_staircases] or [Building::connect_elevators].
+    pub fn get_travel_time(&self, id_room_1: &str, id_room_2: &str) -> Option<Duration> {
+        return self.travel_times.get(&connection_key(id_room_1, id_room_2)).copied();
+    }
+
+    /// Sets the [coordinates](Coordinates) of a specific room.
+    /// <br/>Returns an error if the room does not exist or if one of the coordinates is infinite or NaN.
+    pub fn set_coordinates(&mut self, id: &str, coordinates: Coordinates) -> Result<(), BuildingError> {
+        self.find_node_index(id)?;
+        if !(coordinates.x.is_finite() & coordinates.y.is_finite() & coordinates.z.is_finite()) {
+            return Err(BuildingError::InvalidCoordinates(id.to_string()));
+        }
+        self.room_infos.get_mut(id).unwrap().coordinates = Some(coordinates);
+        return Ok(());
+    }
+
+    /// Gets the [coordinates](Coordinates) of a specific room.
+    /// <br/>Returns None if the room could not be found or if no coordinates were set.
+    pub fn get_coordinates(&self, id: &str) -> Option<Coordinates> {
+        return self.room_infos.get(id)?.coordinates;
+    }
+
+    /// Sets the length in m of the connection between two rooms, e.g. of a long corridor.
+    /// <br/>Returns an error if one of the rooms does not exist, if the rooms are not connected or if the length
+    /// is negative, infinite or NaN.
+    pub fn set_connection_length(&mut self, id_room_1: &str, id_room_2: &str, length: f64) -> Result<(), BuildingError> {
+        let node1 = self.find_node_index(id_room_1)?;
+        let node2 = self.find_node_index(id_room_2)?;
+        if self.floors.find_edge(node1, node2).is_none() {
+            return Err(BuildingError::UnknownConnection(id_room_1.to_string(), id_room_2.to_string()));
+        }
+        // a negative length would be a negative movement time and a negative weight of the shortest paths
+        if !(length.is_finite() & (length >= 0.0)) {
+            return Err(BuildingError::InvalidLength(id_room_1.to_string(), id_room_2.to_string()));
+        }
+        self.connection_lengths.insert(connection_key(id_room_1, id_room_2), length);
+        return Ok(());
+    }
+
+    /// Gets the length in m of the connection between two rooms.
+    /// <br/>If no length was set via [Building::set_connection_length], the length is the distance between the
+    /// coordinates of the rooms. Returns None if the rooms are not connected or if neither a length nor the
+    /// coordinates of both rooms were set.
+    pub fn get_connection_length(&self, id_room_1: &str, id_room_2: &str) -> Option<f64> {
+        let node1 = self.node_index(id_room_1)?;
+        let node2 = self.node_index(id_room_2)?;
+        self.floors.find_edge(node1, node2)?;
+        if let Some(length) = self.connection_lengths.get(&connection_key(id_room_1, id_room_2)) {
+            return Some(*length);
+        }
+        let coordinates_1 = self.get_coordinates(id_room_1)?;
+        let coordinates_2 = self.get_coordinates(id_room_2)?;
+        return Some(coordinates_1.distance(&coordinates_2));
+    }
+
+    /// Gets the length of the connection between two rooms, if it was set via [Building::set_connection_length].
+    pub(crate) fn get_explicit_connection_length(&self, id_room_1: &str, id_room_2: &str) -> Option<f64> {
+        return self.connection_lengths.get(&connection_key(id_room_1, id_room_2)).copied();
+    }
+
+    /// Connects the [Staircase] with the id count `id_count` on floor `level_1` with the staircase with the same id count on floor `level_2`.
+    /// <br/>The floors have to be adjacent, otherwise an error is returned.
+    pub fn connect_staircases(&mut self, id_count: i32, level_1: i32, level_2: i32, travel_time: Duration) -> Result<(), BuildingError> {
+        if (level_1 - level_2).abs() != 1 {
+            return Err(BuildingError::FloorsNotAdjacent(level_1, level_2));
+        }
+        let id_room_1 = Staircase::new(id_count, floor_prefix(level_1).as_str()).id;
+        let id_room_2 = Staircase::new(id_count, floor_prefix(level_2).as_str()).id;
+        let edge_data = Arc::new(Stairs::new(id_count));
+        return self.new_floor_connection(id_room_1, id_room_2, edge_data, travel_time);
+    }
+
+    /// Connects the [Elevator] with the id count `id_count` on floor `level_1` with the elevator with the same id count on floor `level_2`.
+    /// <br/>The floors have to be adjacent, otherwise an error is returned.
+    pub fn connect_elevators(&mut self, id_count: i32, level_1: i32, level_2: i32, travel_time: Duration) -> Result<(), BuildingError> {
+        if (level_1 - level_2).abs() != 1 {
+            return Err(BuildingError::FloorsNotAdjacent(level_1, level_2));
+        }
+        let id_room_1 = Elevator::new(id_count, floor_prefix(level_1).as_str()).id;
+        let id_room_2 = Elevator::new(id_count, floor_prefix(level_2).as_str()).id;
+        let edge_data = Arc::new(Shaft::new(id_count));
+        return self.new_floor_connection(id_room_1, id_room_2, edge_data, travel_time);
+    }
+
+    fn new_floor_connection(&mut self, id_room_1: String, id_room_2: String, edge_data: Arc<dyn EdgeData>, travel_time: Duration) -> Result<(), BuildingError> {
+        self.new_connection(id_room_1.as_str(), id_room_2.as_str(), edge_data)?;
+        self.travel_times.insert(connection_key(id_room_1.as_str(), id_room_2.as_str()), travel_time);
+        return Ok(());
+    }
+
+    /// Connects two rooms with an edge with the given data.
+    /// <br/>Returns an error if one of the rooms does not exist, if the rooms are the same or if they are already connected.
+    fn new_connection(&mut self, id_room_1: &str, id_room_2: &str, edge_data: Arc<dyn EdgeData>) -> Result<(), BuildingError> {
+        if id_room_1 == id_room_2 {
+            return Err(BuildingError::SelfLoop(id_room_1.to_string()));
+        }
+        let node1 = self.find_node_index(id_room_1)?;
+        let node2 = self.find_node_index(id_room_2)?;
+        if self.floors.find_edge(node1, node2).is_some() {
+            return Err(BuildingError::DuplicateConnection(id_room_1.to_string(), id_room_2.to_string()));
+        }
+        self.floors.add_edge(node1, node2, Edge::new(edge_data));
+        return Ok(());
+    }
+
+    /// Gets the index of the node of a specific room in the [graph](Building::get_floors).
+    /// <br/>Returns None if the room could not be found.
+    pub fn node_index(&self, id: &str) -> Option<NodeIndex> {
+        return self.room_indices.get(id).copied();
+    }
+
+    /// Gets the index of the node of a specific room, or an error if the room does not exist.
+    pub(crate) fn find_node_index(&self, id: &str) -> Result<NodeIndex, BuildingError> {
+        return self.node_index(id).ok_or(BuildingError::UnknownRoomId(id.to_string()));
+    }
+
+    /// Adds a node to the graph and remembers its index, the floor it belongs to, its kind, if it has windows and its parent room.
+    /// <br/>Returns an error if a room with the same id already exists.
+    fn add_room_node(&mut self, node: Node<Arc<dyn NodeData>>, level: i32, kind: RoomKind, windows: bool, parent: Option<String>) -> Result<NodeIndex, BuildingError> {
+        let id = node.get_data().get_id();
+        if self.room_indices.contains_key(&id) {
+            return Err(BuildingError::DuplicateRoomId(id));
+        }
+        let index = self.floors.add_node(node);
+        self.room_indices.insert(id.clone(), index);
+        if let Some(parent_id) = &parent {
+            if let Some(parent_info) = self.room_infos.get_mut(parent_id) {
+                parent_info.children.push(id.clone());
+            }
+        }
+        self.room_infos.insert(id, RoomInfo { floor: level, kind, windows, properties: None, parent, children: Vec::new(), room_type: None, rule_policy: None, coordinates: None });
+        return Ok(index);
+    }
+
+    /// Gets the id of the parent room of a specific room, e.g. _RwnD0_ for _RwnD0_RwD3_sub_.
+    /// <br/>Returns None if the room is no sub room or if the room could not be found.
+    pub fn parent(&self, id: &str) -> Option<String> {
+        return self.room_infos.get(id)?.parent.clone();
+    }
+
+    /// Gets the ids of the sub rooms of a specific room.
+    /// <br/>The vector is empty if the room has no sub rooms or if the room could not be found.
+    pub fn children(&self, id: &str) -> Vec<String> {
+        return match self.room_infos.get(id) {
+            Some(info) => info.children.clone(),
+            None => Vec::new(),
+        };
+    }
+
+    /// Gets the ids of all sub rooms of a specific room, their sub rooms and so on.
+    pub fn descendants(&self, id: &str) -> Vec<String> {
+        let mut descendants = self.children(id);
+        let mut position = 0;
+        while position < descendants.len() {
+            let children = self.children(descendants[position].as_str());
+            descendants.extend(children);
+            position += 1;
+        }
+        return descendants;
+    }
+
+    /// Returns true if a specific room is a sub room of another room.
+    pub fn is_sub_room(&self, id: &str) -> bool {
+        return self.parent(id).is_some();
+    }
+
+    /// Sets the parent room of a specific room, or removes it from its parent room if `parent_id` is None.
+    /// <br/>This allows any nesting of rooms, e.g. a desk inside an office inside an open area.
+    /// The connections of the rooms are not changed.
+    /// <br/>Returns an error if one of the rooms does not exist or if the room would become its own ancestor.
+    pub fn set_parent(&mut self, id: &str, parent_id: Option<&str>) -> Result<(), BuildingError> {
+        self.find_node_index(id)?;
+        if let Some(parent_id) = parent_id {
+            self.find_node_index(parent_id)?;
+            if (parent_id == id) | self.descendants(id).iter().any(|descendant| descendant == parent_id) {
+                return Err(BuildingError::CyclicHierarchy(id.to_string()));
+            }
+        }
+        if let Some(old_parent_id) = self.parent(id) {
+            if let Some(old_parent_info) = self.room_infos.get_mut(&old_parent_id) {
+                old_parent_info.children.retain(|child| child != id);
+            }
+        }
+        if let Some(parent_id) = parent_id {
+            self.room_infos.get_mut(parent_id).unwrap().children.push(id.to_string());
         }
+        self.room_infos.get_mut(id).unwrap().parent = parent_id.map(|parent_id| parent_id.to_string());
+        return Ok(());
     }
 
     /// Gets the ids of all adjacent neighbours of a specific room.
     /// <br/>The vector is empty if no neighbours exist or if the node id could not be found.
     pub fn get_neighbours_ids(&mut self, id: String) -> Vec<String> {
-        let mut node_indices = self.floors.node_indices();
-        let node = node_indices.find(|index| self.floors[*index].get_data().get_id() == id);
+        let node = self.node_index(id.as_str());
         let mut ids = Vec::<String>::new();
         if node.is_some() {
             let neighbours = self.floors.neighbors(node.unwrap());
@@ -43,8 +504,7 @@ impl Building {
     /// Gets the ids of all adjacent connection of a specific room.
     /// <br/>The vector is empty if no connection exist or if the node id could not be found.
     pub fn get_connection_ids(&mut self, id: String) -> Vec<String> {
-        let mut node_indices = self.floors.node_indices();
-        let node = node_indices.find(|index| self.floors[*index].get_data().get_id() == id);
+        let node = self.node_index(id.as_str());
         let mut ids = Vec::<String>::new();
         if node.is_some() {
             let neighbours = self.floors.edges(node.unwrap());
@@ -66,99 +526,58 @@ impl Building {
     }
 
     /// Creates a door connection with a specific id, between two rooms with the given ids.
-    /// <br/>Returns ture if successful, false otherwise.
+    /// <br/>Returns an error if one of the rooms does not exist, if the rooms are the same or if they are already connected.
     /// <br/>This should be used, when two rooms are seperated with a door.
-    pub fn new_door_connection(&mut self, id_room_1: String, id_room_2: String, door_id: i32) ->  bool {
-        let node_indices = self.floors.node_indices();
-        let mut node1 = None;
-        let mut node2 = None;
-
-        for index in node_indices {
-            if node1.is_some() & node2.is_some() {
-                break;
-            }
-            let id = self.floors[index].get_data().get_id();
-            if id == id_room_1 {
-                node1 = Some(index);
-                continue;
-            }
-            if id == id_room_2 {
-                node2 = Some(index);
-                continue
-            }
-        }
-
-        if node1.is_none() | node2.is_none() {
-            return false;
-        }
+    pub fn new_door_connection(&mut self, id_room_1: String, id_room_2: String, door_id: i32) -> Result<(), BuildingError> {
         let door_connection = Arc::new(Door::new(door_id, false));
-        let edge = Edge::new(door_connection);
-        self.floors.update_edge(node1.unwrap(), node2.unwrap(), edge);
-        return true;
+        return self.new_connection(id_room_1.as_str(), id_room_2.as_str(), door_connection);
     }
 
     /// Creates a connection with a specific id, between to rooms with given ids.
-    /// <br/>Returns true if successful, false otherwise.
+    /// <br/>Returns an error if one of the rooms does not exist, if the rooms are the same or if they are already connected.
     /// <br/>This method should be used, when two rooms are seperated, but have no doors,
     /// e.g. the connection between two hallways.
-    pub fn new_no_door_connection(&mut self, id_room_1: String, id_room_2: String, door_id: i32) ->  bool {
-        let node_indices = self.floors.node_indices();
-        let mut node1 = None;
-        let mut node2 = None;
-
-        for index in node_indices {
-            if node1.is_some() & node2.is_some() {
-                break;
-            }
-            let id = self.floors[index].get_data().get_id();
-            if id == id_room_1 {
-                node1 = Some(index);
-                continue;
-            }
-            if id == id_room_2 {
-                node2 = Some(index);
-                continue
-            }
-        }
-
-        if node1.is_none() | node2.is_none() {
-            return false;
-        }
-
-        let new_edge = Edge::new(Arc::new(NoDoor::new(door_id, false)));
-
-
-        self.floors.update_edge(node1.unwrap(), node2.unwrap(), new_edge);
-        return true;
-
+    pub fn new_no_door_connection(&mut self, id_room_1: String, id_room_2: String, door_id: i32) -> Result<(), BuildingError> {
+        let no_door_connection = Arc::new(NoDoor::new(door_id, false));
+        return self.new_connection(id_room_1.as_str(), id_room_2.as_str(), no_door_connection);
     }
 
     /// Adds a vector of sensors to a specific room.
-    pub fn add_sensors_to_room(&mut self, node_id: String, mut sensors: Vec<Sensor>) {
+    /// <br/>Returns an error if the room does not exist.
+    pub fn add_sensors_to_room(&mut self, node_id: String, mut sensors: Vec<Sensor>) -> Result<(), BuildingError> {
+        let node_index = self.find_node_index(node_id.as_str())?;
         let mut i = 0;
         for j in 0..sensors.len() {
             let sensor_type =sensors[j].get_sensor_type().get_id().clone();
-            sensors[j].set_id("Sensor_".to_owned() + node_id.as_str() + "_no._" + i.to_string().as_str()+"_of_type_"+sensor_type.as_str());
+            sensors[j].set_id(sensor_id(node_id.as_str(), i, sensor_type.as_str()));
             i = i + 1;
         }
-        let indices = self.floors.node_indices();
-        for node_index in indices {
-            if self.floors[node_index].get_data().get_id() == node_id {
-                self.floors[node_index].add_sensors(sensors.clone());
-            }
-        }
+        self.floors[node_index].add_sensors(sensors);
+        return Ok(());
     }
 
-    /// Adds a [Staircase] to the building.
-    pub fn add_staircase(&mut self, id_count: i32) {
-        let st = Staircase::new(id_count);
+    /// Adds a [Staircase] to the current floor of the building.
+    /// <br/>Returns an error if the staircase already exists.
+    pub fn add_staircase(&mut self, id_count: i32) -> Result<(), BuildingError> {
+        let st = Staircase::new(id_count, floor_prefix(self.current_floor).as_str());
         let new_staircase = Node::new(Arc::new(st));
-        let _ = self.floors.add_node(new_staircase);
+        self.add_room_node(new_staircase, self.current_floor, RoomKind::Staircase, false, None)?;
+        return Ok(());
+    }
+
+    /// Adds an [Elevator] to the current floor of the building.
+    /// <br/>Returns an error if the elevator already exists.
+    pub fn add_elevator(&mut self, id_count: i32) -> Result<(), BuildingError> {
+        let elevator = Elevator::new(id_count, floor_prefix(self.current_floor).as_str());
+        let new_elevator = Node::new(Arc::new(elevator));
+        self.add_room_node(new_elevator, self.current_floor, RoomKind::Elevator, false, None)?;
+        return Ok(());
     }
 
     /// Adds a [room with doors](RoomWithDoors) to the building.
-    pub fn add_room_with_doors(&mut self, id_count: i32, what_sensor_should_be_create: Vec<(u32, SensorType)>, windows: bool, offspring_number_for_sensors:i64) -> i64 {
-        let rwd = RoomWithDoors::new(id_count, false, "".to_string(), windows);
+    /// <br/>Returns the offspring number of the last created sensor, or an error if the room already exists.
+    pub fn add_room_with_doors(&mut self, id_count: i32, what_sensor_should_be_create: Vec<(u32, SensorType)>, windows: bool, offspring_number_for_sensors:i64) -> Result<i64, BuildingError> {
+        let rwd = RoomWithDoors::new(id_count, false, floor_prefix(self.current_floor), windows);
         let mut new_node = Node::new(Arc::new(rwd));
         let mut sensors = Vec::<Sensor>::new();
         let mut offspring_number = offspring_number_for_sensors;
@@ -167,20 +586,21 @@ impl Building {
                 continue
             }
             for i in 0..pair.0 {
-                let sensor_id = "Sensor_".to_owned() + new_node.get_data().get_id().as_str() + "_no._" + i.to_string().as_str()+"_of_type_"+pair.1.get_id().as_str();
+                let sensor_id = sensor_id(new_node.get_data().get_id().as_str(), i as usize, pair.1.get_id().as_str());
                 offspring_number += 1;
                 let sensor = Sensor::new(sensor_id, pair.1.clone(), offspring_number);
                 sensors.push(sensor);
             }
         }
         new_node.add_sensors(sensors);
-        let _ = self.floors.add_node(new_node);
-        return offspring_number;
+        self.add_room_node(new_node, self.current_floor, RoomKind::RoomWithDoors, windows, None)?;
+        return Ok(offspring_number);
     }
 
     /// Adds a [room without doors](RoomWithoutDoors) to the building.
-    pub fn add_room_without_doors(&mut self, id_count: i32, what_sensor_should_be_create: Vec<(u32, SensorType)>, windows: bool, offspring_number_for_sensors:i64) -> i64 {
-        let rwnd = RoomWithoutDoors::new(id_count, false, "".to_string(), windows);
+    /// <br/>Returns the offspring number of the last created sensor, or an error if the room already exists.
+    pub fn add_room_without_doors(&mut self, id_count: i32, what_sensor_should_be_create: Vec<(u32, SensorType)>, windows: bool, offspring_number_for_sensors:i64) -> Result<i64, BuildingError> {
+        let rwnd = RoomWithoutDoors::new(id_count, false, floor_prefix(self.current_floor), windows);
         let mut new_node = Node::new(Arc::new(rwnd));
         let mut sensors = Vec::<Sensor>::new();
         let mut offspring_number = offspring_number_for_sensors;
@@ -189,15 +609,15 @@ impl Building {
                 continue
             }
             for i in 0..pair.0 {
-                let sensor_id = "Sensor_".to_owned() + new_node.get_data().get_id().as_str() + "_no._" + i.to_string().as_str()+"_of_type_"+pair.1.get_id().as_str();
+                let sensor_id = sensor_id(new_node.get_data().get_id().as_str(), i as usize, pair.1.get_id().as_str());
                 offspring_number += 1;
                 let sensor = Sensor::new(sensor_id, pair.1.clone(), offspring_number);
                 sensors.push(sensor);
             }
         }
         new_node.add_sensors(sensors);
-        let _ = self.floors.add_node(new_node);
-        return offspring_number;
+        self.add_room_node(new_node, self.current_floor, RoomKind::RoomWithoutDoors, windows, None)?;
+        return Ok(offspring_number);
     }
 
     /// Adds a specific number of "[rooms with doors](RoomWithDoors)" to a specific room,
@@ -205,12 +625,17 @@ impl Building {
     /// creates the specific number of [`Sensors`](rubalosim::sensor::Sensor) of the specific
     /// [`SensorType`](rubalosim::sensor::SensorType) for each room.
     /// <br/>The sensors in a room will have an id of the following pattern: _room-id_ _ sensor _ _number_ _ of_type _ _number_.
-    pub fn add_sub_rooms_with_doors(&mut self, number_of_rooms: i32, parent_id: String, what_sensor_should_be_create: Vec<(u32, SensorType)>, windows: bool, offspring_number_sensors:i64) -> (bool, i64) {
-        let mut node_indices = self.floors.node_indices();
-        let parent_index = node_indices.find(|index| self.floors[*index].get_data().get_id() == parent_id);
-        if parent_index.is_none() {
-            return (false, offspring_number_sensors);
+    /// <br/>Returns the offspring number of the last created sensor, or an error if the parent room does not exist
+    /// or if one of the sub rooms already exists. In case of an error no sub room is added.
+    pub fn add_sub_rooms_with_doors(&mut self, number_of_rooms: i32, parent_id: String, what_sensor_should_be_create: Vec<(u32, SensorType)>, windows: bool, offspring_number_sensors:i64) -> Result<i64, BuildingError> {
+        let parent_index = self.find_node_index(parent_id.as_str())?;
+        for id in 0..number_of_rooms {
+            let sub_room_id = RoomWithDoors::new(id, true, parent_id.clone(), windows).id;
+            if self.room_indices.contains_key(&sub_room_id) {
+                return Err(BuildingError::DuplicateRoomId(sub_room_id));
+            }
         }
+        let level = self.get_floor_of_room(parent_id.as_str()).unwrap_or(self.current_floor);
         let mut offspring_number = offspring_number_sensors;
         for id in 0..number_of_rooms {
             let s_rwd = RoomWithDoors::new(id, true, parent_id.clone(), windows);
@@ -221,18 +646,18 @@ impl Building {
                     continue
                 }
                 for i in 0..pair.0 {
-                    let sensor_id = "Sensor_".to_owned() + new_node.get_data().get_id().as_str() + "_no._" + i.to_string().as_str()+"_of_type_"+pair.1.get_id().as_str();
+                    let sensor_id = sensor_id(new_node.get_data().get_id().as_str(), i as usize, pair.1.get_id().as_str());
                     offspring_number += 1;
                     let sensor = Sensor::new(sensor_id, pair.1.clone(), offspring_number);
                     sensors.push(sensor);
                 }
             }
             new_node.add_sensors(sensors);
-            let node = self.floors.add_node(new_node);
+            let node = self.add_room_node(new_node, level, RoomKind::RoomWithDoors, windows, Some(parent_id.clone()))?;
             let edge = Edge::new(Arc::new(Door::new(id, true)));
-            self.floors.add_edge(parent_index.unwrap(), node, edge);
+            self.floors.add_edge(parent_index, node, edge);
         }
-        return (true, offspring_number);
+        return Ok(offspring_number);
     }
 
 
@@ -241,12 +666,17 @@ impl Building {
     /// creates the specific number of [`Sensors`](rubalosim::sensor::Sensor) of the specific
     /// [`SensorType`](rubalosim::sensor::SensorType) for each room.
     /// <br/>The sensors in a room will have an id of the following pattern: _room-id_ _ sensor _ _number_ _ of_type _ _sensor type id_.
-    pub fn add_sub_rooms_without_doors(&mut self, number_of_rooms: i32, parent_id: String, what_sensor_should_be_create: Vec<(u32, SensorType)>, windows: bool, offspring_number_sensors:i64) -> (bool, i64) {
-        let mut node_indices = self.floors.node_indices();
-        let parent_index = node_indices.find(|index| self.floors[*index].get_data().get_id() == parent_id);
-        if parent_index.is_none() {
-            return (false, offspring_number_sensors);
+    /// <br/>Returns the offspring number of the last created sensor, or an error if the parent room does not exist
+    /// or if one of the sub rooms already exists. In case of an error no sub room is added.
+    pub fn add_sub_rooms_without_doors(&mut self, number_of_rooms: i32, parent_id: String, what_sensor_should_be_create: Vec<(u32, SensorType)>, windows: bool, offspring_number_sensors:i64) -> Result<i64, BuildingError> {
+        let parent_index = self.find_node_index(parent_id.as_str())?;
+        for id in 0..number_of_rooms {
+            let sub_room_id = RoomWithoutDoors::new(id, true, parent_id.clone(), windows).id;
+            if self.room_indices.contains_key(&sub_room_id) {
+                return Err(BuildingError::DuplicateRoomId(sub_room_id));
+            }
         }
+        let level = self.get_floor_of_room(parent_id.as_str()).unwrap_or(self.current_floor);
         let mut offspring_number = offspring_number_sensors;
         for id in 0..number_of_rooms {
             let s_rwnd = RoomWithoutDoors::new(id, true, parent_id.clone(), windows);
@@ -258,7 +688,7 @@ impl Building {
                     continue
                 }
                 for i in 0..pair.0 {
-                    let sensor_id = "Sensor_".to_owned() + new_node.get_data().get_id().as_str() + "_no._" + i.to_string().as_str()+"_of_type_"+pair.1.get_id().as_str();
+                    let sensor_id = sensor_id(new_node.get_data().get_id().as_str(), i as usize, pair.1.get_id().as_str());
                     offspring_number += 1;
                     let sensor = Sensor::new(sensor_id, pair.1.clone(), offspring_number);
                     sensors.push(sensor);
@@ -266,142 +696,1258 @@ impl Building {
             }
             new_node.add_sensors(sensors);
 
-            let node = self.floors.add_node(new_node);
-            self.floors.add_edge(parent_index.unwrap(), node, Edge::new(Arc::new(NoDoor::new(id, true))));
+            let node = self.add_room_node(new_node, level, RoomKind::RoomWithoutDoors, windows, Some(parent_id.clone()))?;
+            self.floors.add_edge(parent_index, node, Edge::new(Arc::new(NoDoor::new(id, true))));
         }
-        return (true, offspring_number);
+        return Ok(offspring_number);
     }
 }
 
-impl UnderlyingStructure for Building {
-    fn get_graph_structure(&self) -> &Graph<Node<Arc<dyn NodeData>>, Edge<Arc<dyn EdgeData>>, Undirected> {
-        return self.get_floors();
-    }
-
-    // make this changeable
-    // Why not with NodeIndex
-    fn get_start_nodes(&self) -> Vec<NodeIndex> {
-        let mut res = Vec::new();
-        let node_indices = self.floors.node_indices();
-        for i in node_indices {
-            let id = self.floors[i].get_data().get_id();
-            if id.starts_with("S") {
-                res.push(i);
+impl Building {
+    /// Removes a room and all of its connections from the building.
+    /// <br/>Sub rooms of the room are not removed, they keep the removed room as parent, see [Building::validate].
+    /// <br/>Returns an error if the room does not exist.
+    pub fn remove_room(&mut self, id: &str) -> Result<(), BuildingError> {
+        let index = self.find_node_index(id)?;
+        let _ = self.floors.remove_node(index);
+        // the last node of the graph takes the index of the removed node
+        if index.index() < self.floors.node_count() {
+            let moved_id = self.floors[index].get_data().get_id();
+            self.room_indices.insert(moved_id, index);
+        }
+        self.room_indices.remove(id);
+        // the sub rooms keep the id of their parent, so that the missing parent is found by validate
+        if let Some(parent_id) = self.parent(id) {
+            if let Some(parent_info) = self.room_infos.get_mut(&parent_id) {
+                parent_info.children.retain(|child| child != id);
             }
         }
-        return res;
+        self.room_infos.remove(id);
+        self.travel_times.retain(|(id_room_1, id_room_2), _| (id_room_1 != id) & (id_room_2 != id));
+        self.connection_lengths.retain(|(id_room_1, id_room_2), _| (id_room_1 != id) & (id_room_2 != id));
+        self.door_sensors.retain(|(id_room_1, id_room_2), _| (id_room_1 != id) & (id_room_2 != id));
+        self.door_schedules.retain(|(id_room_1, id_room_2), _| (id_room_1 != id) & (id_room_2 != id));
+        for zone in self.lighting_zones.iter_mut() {
+            zone.desks.retain(|desk| desk != id);
+        }
+        self.lighting_zones.retain(|zone| zone.id != id);
+        for rooms in [&mut self.entrances, &mut self.exits].into_iter().flatten() {
+            rooms.retain(|room_id| room_id != id);
+        }
+        if let Some(destinations) = &mut self.destinations {
+            destinations.retain(|(room_id, _)| room_id != id);
+        }
+        return Ok(());
     }
 
-    // this is just the case in this case
-    fn get_end_nodes(&self) -> Vec<NodeIndex> {
-        self.get_start_nodes()
+    /// Removes the connection between two rooms.
+    /// <br/>Returns an error if one of the rooms does not exist or if the rooms are not connected.
+    pub fn remove_connection(&mut self, id_room_1: &str, id_room_2: &str) -> Result<(), BuildingError> {
+        let node1 = self.find_node_index(id_room_1)?;
+        let node2 = self.find_node_index(id_room_2)?;
+        let edge = self.floors.find_edge(node1, node2);
+        if edge.is_none() {
+            return Err(BuildingError::UnknownConnection(id_room_1.to_string(), id_room_2.to_string()));
+        }
+        let _ = self.floors.remove_edge(edge.unwrap());
+        self.travel_times.remove(&connection_key(id_room_1, id_room_2));
+        self.connection_lengths.remove(&connection_key(id_room_1, id_room_2));
+        self.door_sensors.remove(&connection_key(id_room_1, id_room_2));
+        self.door_schedules.remove(&connection_key(id_room_1, id_room_2));
+        return Ok(());
     }
 
+    /// Renames a room. The room id in the ids of its sensors is replaced, their numbers and sensor types are kept.
+    /// <br/>Sub rooms whose ids start with the id of their parent are renamed as well,
+    /// e.g. _RwnD0_RwD3_sub_ becomes _Kitchen_RwD3_sub_ if _RwnD0_ is renamed to _Kitchen_.
+    /// <br/>Returns an error if the room does not exist or if a room with the new id already exists.
+    pub fn rename_room(&mut self, old_id: &str, new_id: &str) -> Result<(), BuildingError> {
+        self.find_node_index(old_id)?;
+        let mut renaming = vec![(old_id.to_string(), new_id.to_string())];
+        let mut position = 0;
+        while position < renaming.len() {
+            let (parent_old_id, parent_new_id) = renaming[position].clone();
+            for id in self.children(parent_old_id.as_str()) {
+                if id.starts_with((parent_old_id.clone() + "_").as_str()) {
+                    let new_sub_room_id = parent_new_id.clone() + &id[parent_old_id.len()..];
+                    renaming.push((id, new_sub_room_id));
+                }
+            }
+            position += 1;
+        }
+        for (old, new) in &renaming {
+            if (old != new) & self.room_indices.contains_key(new) {
+                return Err(BuildingError::DuplicateRoomId(new.clone()));
+            }
+        }
 
-    fn get_nodes_to_move_to(&self) -> Vec<NodeIndex> {
-        let mut res = Vec::new();
-        let node_indices = self.floors.node_indices();
-        for i in node_indices {
-            let id = self.floors[i].get_data().get_id();
-            if id.contains("_sub") {
-                res.push(i);
+        let new_ids: HashMap<String, String> = renaming.iter().cloned().collect();
+        for info in self.room_infos.values_mut() {
+            if let Some(new_parent_id) = info.parent.as_ref().and_then(|parent_id| new_ids.get(parent_id)) {
+                info.parent = Some(new_parent_id.clone());
+            }
+            for child in info.children.iter_mut() {
+                if let Some(new_child_id) = new_ids.get(child) {
+                    *child = new_child_id.clone();
+                }
             }
         }
-        return res;
-    }
-}
 
-// maybe not necessary
-// right now it is not: 22-10-28
-pub struct Floor {
-    id: String
-}
+        for (old, new) in renaming {
+            let index = self.room_indices.remove(&old).unwrap();
+            let info = self.room_infos.remove(&old).unwrap();
+            let mut sensors = self.floors[index].get_sensors().to_vec();
+            for sensor in sensors.iter_mut() {
+                // only the room id is replaced, the number and type of the sensor are kept
+                if let Ok(id) = SensorId::parse(sensor.get_id().as_str()) {
+                    sensor.set_id(sensor_id(new.as_str(), id.get_number(), id.get_sensor_type_id()));
+                }
+            }
+            let mut new_node = Node::new(create_node_data(info.kind, new.clone(), info.windows));
+            new_node.add_sensors(sensors);
+            self.floors[index] = new_node;
+            self.room_indices.insert(new.clone(), index);
+            self.room_infos.insert(new.clone(), info);
 
-impl NodeData for Floor {
-    fn get_id(&self) -> String {
-        return self.id.clone();
+            rename_in_connections(&mut self.travel_times, old.as_str(), new.as_str());
+            rename_in_connections(&mut self.connection_lengths, old.as_str(), new.as_str());
+            rename_in_connections(&mut self.door_sensors, old.as_str(), new.as_str());
+            rename_in_connections(&mut self.door_schedules, old.as_str(), new.as_str());
+            for zone in self.lighting_zones.iter_mut() {
+                for desk in zone.desks.iter_mut().filter(|desk| **desk == old) {
+                    *desk = new.clone();
+                }
+                if zone.id == old {
+                    zone.id = new.clone();
+                }
+            }
+            for ((id_room_1, id_room_2), door_sensors) in self.door_sensors.iter_mut() {
+                if (*id_room_1 != new) & (*id_room_2 != new) {
+                    continue
+                }
+                for (i, (kind, sensor)) in door_sensors.iter_mut().enumerate() {
+                    if let DoorSensorKind::PeopleCounter(room_id) = kind {
+                        if *room_id == old {
+                            *room_id = new.clone();
+                        }
+                    }
+                    let sensor_type = sensor.get_sensor_type().get_id();
+                    sensor.set_id(sensor_id(door_sensor_room_id(id_room_1, id_room_2).as_str(), i, sensor_type.as_str()));
+                }
+            }
+            for rooms in [&mut self.entrances, &mut self.exits].into_iter().flatten() {
+                for room_id in rooms.iter_mut().filter(|room_id| **room_id == old) {
+                    *room_id = new.clone();
+                }
+            }
+            if let Some(destinations) = &mut self.destinations {
+                for (room_id, _) in destinations.iter_mut().filter(|(room_id, _)| *room_id == old) {
+                    *room_id = new.clone();
+                }
+            }
+        }
+        return Ok(());
     }
-}
-
-
-struct Staircase {
-    id: String
-}
 
-impl Staircase {
-    fn new(id_count: i32) -> Self {
-        Staircase {
-            id: "S".to_owned() + id_count.to_string().as_str(),
+    /// Adds sensors of a specific kind to the door between two rooms, e.g. door contacts or people counters.
+    /// <br/>The sensors in a door will have an id of the following pattern: _Sensor_ _ _Door_ _ _room-id_ _ _room-id_ _ no. _ _number_ _ of_type _ _sensor-type-id_,
+    /// with the ids of the rooms in alphabetical order.
+    /// <br/>The sensors should have a sensor type with the payload _contact_ or _counter_, their messages are created by
+    /// [create_door_sensor_events](crate::movement::create_door_sensor_events).
+    /// <br/>Returns an error if one of the rooms does not exist, if the rooms are not connected via a door or if
+    /// the room counted by a [people counter](DoorSensorKind::PeopleCounter) is none of the two rooms.
+    pub fn add_sensors_to_door(&mut self, id_room_1: &str, id_room_2: &str, kind: DoorSensorKind, mut sensors: Vec<Sensor>) -> Result<(), BuildingError> {
+        self.check_door(id_room_1, id_room_2)?;
+        if let DoorSensorKind::PeopleCounter(room_id) = &kind {
+            if (room_id != id_room_1) & (room_id != id_room_2) {
+                return Err(BuildingError::UnknownRoomId(room_id.clone()));
+            }
+        }
+        let key = connection_key(id_room_1, id_room_2);
+        let door_sensors = self.door_sensors.entry(key.clone()).or_default();
+        for sensor in sensors.iter_mut() {
+            let sensor_type = sensor.get_sensor_type().get_id();
+            sensor.set_id(sensor_id(door_sensor_room_id(key.0.as_str(), key.1.as_str()).as_str(), door_sensors.len(), sensor_type.as_str()));
+            door_sensors.push((kind.clone(), sensor.clone()));
         }
+        return Ok(());
     }
-}
 
-impl NodeData for Staircase {
-    fn get_id(&self) -> String {
-        return self.id.clone();
+    /// Gets the sensors at the door between two rooms with their kind.
+    /// <br/>The vector is empty if the door has no sensors or if the rooms are not connected.
+    pub fn get_door_sensors(&self, id_room_1: &str, id_room_2: &str) -> Vec<(DoorSensorKind, Sensor)> {
+        return self.door_sensors.get(&connection_key(id_room_1, id_room_2)).cloned().unwrap_or_default();
     }
-}
-
 
-struct RoomWithDoors {
-    id: String,
-    windows: bool,
-}
+    /// Gets the ids of the rooms of all doors with sensors.
+    pub fn get_doors_with_sensors(&self) -> Vec<(String, String)> {
+        let mut doors: Vec<(String, String)> = self.door_sensors.iter()
+            .filter(|(_, door_sensors)| !door_sensors.is_empty())
+            .map(|(key, _)| key.clone()).collect();
+        doors.sort();
+        return doors;
+    }
 
-impl RoomWithDoors {
-    fn new(id_count: i32, sub_room: bool, parent_id: String, windows: bool) -> Self {
-        let sub_room_flag = "_sub";
-        let mut id = "RwD".to_owned() + id_count.to_string().as_str();
-        if sub_room {
-            id = parent_id + "_" +  id.as_str() + sub_room_flag;
+    /// Adds a [lighting zone](LightingZone) with a specific id to a room, whose lights are shared by some desks,
+    /// e.g. a luminaire that spans several desks of an open-plan area.
+    /// <br/>The zone is a node of kind [RoomKind::LightingZone] with the sensors of the lights, which is a sub room of the room
+    /// and connected with it without a door, so that the simulator knows its sensors. It is no default destination.
+    /// <br/>The sensors of the zone will have an id of the following pattern: _Sensor_ _ _zone-id_ _ no. _ _number_ _ of_type _ _sensor-type-id_.
+    /// <br/>Returns an error if the room or one of the desks does not exist or if a room with the id already exists.
+    pub fn add_lighting_zone(&mut self, id: String, room_id: &str, desks: Vec<String>, sensors: Vec<Sensor>) -> Result<(), BuildingError> {
+        if self.room_indices.contains_key(&id) {
+            return Err(BuildingError::DuplicateRoomId(id));
         }
-        RoomWithDoors{id, windows}
+        let level = self.get_floor_of_room(room_id).ok_or(BuildingError::UnknownRoomId(room_id.to_string()))?;
+        for desk in &desks {
+            self.find_node_index(desk)?;
+        }
+        self.insert_room(id.clone(), RoomKind::LightingZone, level, false, Some(room_id.to_string()), Vec::new())?;
+        self.insert_connection(room_id, id.as_str(), ConnectionType::NoDoor, "NoDoor_".to_owned() + id.as_str(), None)?;
+        self.add_sensors_to_room(id.clone(), sensors)?;
+        self.lighting_zones.push(LightingZone { id, desks });
+        return Ok(());
+    }
 
+    /// Gets all lighting zones in the order they were added.
+    pub fn get_lighting_zones(&self) -> &Vec<LightingZone> {
+        return &self.lighting_zones;
     }
 
-    pub fn has_windows(&self) -> bool {
-        self.windows
+    /// Gets the lighting zone with a specific id.
+    /// <br/>Returns None if there is no such zone.
+    pub fn get_lighting_zone(&self, id: &str) -> Option<&LightingZone> {
+        return self.lighting_zones.iter().find(|zone| zone.id == id);
     }
-}
 
-impl NodeData for RoomWithDoors {
-    fn get_id(&self) -> String {
-        return self.id.clone();
+    /// Checks that two rooms are connected via a door.
+    /// <br/>Returns an error if one of the rooms does not exist or if the rooms are not connected via a door.
+    fn check_door(&self, id_room_1: &str, id_room_2: &str) -> Result<(), BuildingError> {
+        let node1 = self.find_node_index(id_room_1)?;
+        let node2 = self.find_node_index(id_room_2)?;
+        let edge = self.floors.find_edge(node1, node2).ok_or(BuildingError::UnknownConnection(id_room_1.to_string(), id_room_2.to_string()))?;
+        if ConnectionType::from_edge_id(self.floors[edge].get_data().get_id().as_str()) != Some(ConnectionType::Door) {
+            return Err(BuildingError::NoDoorConnection(id_room_1.to_string(), id_room_2.to_string()));
+        }
+        return Ok(());
     }
-}
 
+    /// Sets the state of the door between two rooms outside of its periods, see [DoorSchedule].
+    /// <br/>Returns an error if one of the rooms does not exist or if the rooms are not connected via a door.
+    pub fn set_door_state(&mut self, id_room_1: &str, id_room_2: &str, state: DoorState) -> Result<(), BuildingError> {
+        self.check_door(id_room_1, id_room_2)?;
+        let schedule = self.door_schedules.entry(connection_key(id_room_1, id_room_2)).or_default();
+        schedule.state = state;
+        return Ok(());
+    }
 
-struct RoomWithoutDoors {
-    id: String,
-    windows: bool,
-}
+    /// Adds a period of the day, in which the door between two rooms is in a specific state,
+    /// e.g. a door that is locked from 20:00 to 06:00.
+    /// <br/>Returns an error if one of the rooms does not exist or if the rooms are not connected via a door.
+    pub fn add_door_period(&mut self, id_room_1: &str, id_room_2: &str, period: DoorPeriod) -> Result<(), BuildingError> {
+        self.check_door(id_room_1, id_room_2)?;
+        self.door_schedules.entry(connection_key(id_room_1, id_room_2)).or_default().add_period(period);
+        return Ok(());
+    }
 
-impl RoomWithoutDoors {
-    fn new(id_count: i32, sub_room: bool, parent_id: String, windows: bool) -> Self {
-        let sub_room_flag = "_sub";
-        let mut id = "RwnD".to_owned() + id_count.to_string().as_str();
-        if sub_room {
-            id = parent_id + "_" + id.as_str() + sub_room_flag;
-        }
-        RoomWithoutDoors {
-            id,
-            windows
+    /// Replaces the schedule of the door between two rooms.
+    /// <br/>Returns an error if one of the rooms does not exist or if the rooms are not connected via a door.
+    pub fn set_door_schedule(&mut self, id_room_1: &str, id_room_2: &str, schedule: DoorSchedule) -> Result<(), BuildingError> {
+        self.check_door(id_room_1, id_room_2)?;
+        self.door_schedules.insert(connection_key(id_room_1, id_room_2), schedule);
+        return Ok(());
+    }
+
+    /// Gets the schedule of the door between two rooms, which is [closed all day](DoorSchedule::default) if it was not set.
+    /// <br/>Returns None if the rooms are not connected via a door.
+    pub fn get_door_schedule(&self, id_room_1: &str, id_room_2: &str) -> Option<DoorSchedule> {
+        if self.check_door(id_room_1, id_room_2).is_err() {
+            return None;
         }
+        return Some(self.door_schedules.get(&connection_key(id_room_1, id_room_2)).cloned().unwrap_or_default());
     }
 
-    pub fn has_windows(&self) -> bool {
-        self.windows
+    /// Gets the state of the door between two rooms at a specific time.
+    /// <br/>Returns None if the rooms are not connected via a door.
+    pub fn get_door_state(&self, id_room_1: &str, id_room_2: &str, time: NaiveTime) -> Option<DoorState> {
+        return self.get_door_schedule(id_room_1, id_room_2).map(|schedule| schedule.get_state(time));
     }
-}
 
-impl NodeData for RoomWithoutDoors {
-    fn get_id(&self) -> String {
-        return self.id.clone();
+    /// Checks whether a movable object can get from one room directly into another one at a specific time,
+    /// that is whether the rooms are connected and the connection is no [locked](DoorState::Locked) door.
+    pub fn is_passable(&self, id_room_1: &str, id_room_2: &str, time: NaiveTime) -> bool {
+        let connected = match (self.room_indices.get(id_room_1), self.room_indices.get(id_room_2)) {
+            (Some(node1), Some(node2)) => self.floors.find_edge(*node1, *node2).is_some(),
+            _ => false
+        };
+        return connected & (self.get_door_state(id_room_1, id_room_2, time) != Some(DoorState::Locked));
     }
-}
 
-struct Door {
-    id: String,
+    /// Locks the doors of all sub rooms within a period of the day, e.g. from 20:00 to 06:00,
+    /// so that the sub rooms can not be entered after hours.
+    /// <br/>Sub rooms without doors can not be locked.
+    pub fn lock_sub_rooms(&mut self, start: NaiveTime, end: NaiveTime) {
+        let mut doors = Vec::<(String, String)>::new();
+        for edge in self.floors.edge_indices() {
+            if ConnectionType::from_edge_id(self.floors[edge].get_data().get_id().as_str()) != Some(ConnectionType::Door) {
+                continue
+            }
+            let (node1, node2) = self.floors.edge_endpoints(edge).unwrap();
+            let id_room_1 = self.floors[node1].get_data().get_id();
+            let id_room_2 = self.floors[node2].get_data().get_id();
+            if self.is_sub_room(id_room_1.as_str()) | self.is_sub_room(id_room_2.as_str()) {
+                doors.push(connection_key(id_room_1.as_str(), id_room_2.as_str()));
+            }
+        }
+        for key in doors {
+            self.door_schedules.entry(key).or_default().add_period(DoorPeriod::new(start, end, DoorState::Locked));
+        }
+    }
+
+    /// Sets whether a specific room has windows.
+    /// <br/>If the room has [properties](RoomProperties), removing the windows removes the window area and the orientation as well.
+    /// <br/>Returns an error if the room does not exist or if it should get windows, but its properties have no window area.
+    pub fn set_windows(&mut self, id: &str, windows: bool) -> Result<(), BuildingError> {
+        let index = self.find_node_index(id)?;
+        if let Some(properties) = self.room_infos.get_mut(id).unwrap().properties.as_mut() {
+            if windows & (properties.window_area <= 0.0) {
+                return Err(BuildingError::NoWindowArea(id.to_string()));
+            }
+            if !windows {
+                properties.window_area = 0.0;
+                properties.orientation = None;
+            }
+        }
+        self.replace_windows(index, id, windows);
+        return Ok(());
+    }
+
+    /// Replaces the node of a specific room by a node with or without windows, keeping its sensors.
+    fn replace_windows(&mut self, index: NodeIndex, id: &str, windows: bool) {
+        let info = self.room_infos.get_mut(id).unwrap();
+        if info.windows == windows {
+            return;
+        }
+        info.windows = windows;
+        let mut new_node = Node::new(create_node_data(info.kind, id.to_string(), windows));
+        new_node.add_sensors(self.floors[index].get_sensors().to_vec());
+        self.floors[index] = new_node;
+    }
+
+    /// Removes all sensors from a specific room and returns them.
+    /// <br/>Returns an error if the room does not exist.
+    pub fn remove_sensors_from_room(&mut self, id: &str) -> Result<Vec<Sensor>, BuildingError> {
+        let index = self.find_node_index(id)?;
+        let sensors = self.floors[index].get_sensors().to_vec();
+        self.floors[index] = Node::new(self.floors[index].get_data().clone());
+        return Ok(sensors);
+    }
+
+    /// Replaces all sensors of a specific room with the given sensors.
+    /// The ids of the sensors are changed as in [Building::add_sensors_to_room], their numbers are kept.
+    /// <br/>Returns the removed sensors, or an error if the room does not exist.
+    pub fn replace_sensors_of_room(&mut self, id: &str, sensors: Vec<Sensor>) -> Result<Vec<Sensor>, BuildingError> {
+        let removed_sensors = self.remove_sensors_from_room(id)?;
+        self.add_sensors_to_room(id.to_string(), sensors)?;
+        return Ok(removed_sensors);
+    }
+
+    /// Moves all sensors of the room `from_id` to the room `to_id`, where they are added to the existing sensors.
+    /// The ids of the sensors are changed accordingly, their numbers are kept.
+    /// <br/>Returns an error if one of the rooms does not exist.
+    pub fn move_sensors(&mut self, from_id: &str, to_id: &str) -> Result<(), BuildingError> {
+        let to_index = self.find_node_index(to_id)?;
+        let mut sensors = self.remove_sensors_from_room(from_id)?;
+        let number_of_existing_sensors = self.floors[to_index].get_sensors().len();
+        for i in 0..sensors.len() {
+            let sensor_type = sensors[i].get_sensor_type().get_id();
+            sensors[i].set_id(sensor_id(to_id, number_of_existing_sensors + i, sensor_type.as_str()));
+        }
+        self.floors[to_index].add_sensors(sensors);
+        return Ok(());
+    }
+}
+
+impl Building {
+    /// Checks the structure of the building and returns a [ValidationReport] with all problems found.
+    /// <br/>It checks for
+    /// - rooms that are not connected with the rest of the building,
+    /// - rooms that can not be reached from any start node,
+    /// - sub rooms whose parent room does not exist,
+    /// - rooms, except staircases and elevators, without any sensors.
+    ///
+    /// Parallel connections between the same two rooms are not checked, because they can not be created:
+    /// connecting two rooms twice returns [BuildingError::DuplicateConnection].
+    ///
+    /// This should be done before the building is given to [`Parameters`](rubalosim::simulator::parameters::Parameters).
+    pub fn validate(&self) -> ValidationReport {
+        let mut problems = Vec::<ValidationProblem>::new();
+
+        let components = self.get_components();
+        if components.len() > 1 {
+            problems.push(ValidationProblem::DisconnectedComponents(components));
+        }
+
+        let start_nodes = self.get_start_nodes();
+        if start_nodes.is_empty() {
+            problems.push(ValidationProblem::NoStartNodes);
+        } else {
+            let mut reachable = vec![false; self.floors.node_count()];
+            let mut stack = start_nodes.clone();
+            while let Some(index) = stack.pop() {
+                if reachable[index.index()] {
+                    continue
+                }
+                reachable[index.index()] = true;
+                stack.extend(self.floors.neighbors(index));
+            }
+            for index in self.floors.node_indices() {
+                if !reachable[index.index()] {
+                    problems.push(ValidationProblem::UnreachableRoom(self.floors[index].get_data().get_id()));
+                }
+            }
+        }
+
+        for index in self.floors.node_indices() {
+            let id = self.floors[index].get_data().get_id();
+            if let Some(parent_id) = self.parent(id.as_str()) {
+                if self.node_index(parent_id.as_str()).is_none() {
+                    problems.push(ValidationProblem::MissingParent(id.clone(), parent_id));
+                }
+            }
+            let kind = self.get_room_kind(id.as_str());
+            let is_floor_connection = (kind == Some(RoomKind::Staircase)) | (kind == Some(RoomKind::Elevator));
+            if !is_floor_connection & self.floors[index].get_sensors().is_empty() {
+                problems.push(ValidationProblem::RoomWithoutSensors(id));
+            }
+        }
+
+        return ValidationReport { problems };
+    }
+
+    /// Gets the ids of the rooms of every connected component of the building.
+    fn get_components(&self) -> Vec<Vec<String>> {
+        let mut visited = vec![false; self.floors.node_count()];
+        let mut components = Vec::<Vec<String>>::new();
+        for start in self.floors.node_indices() {
+            if visited[start.index()] {
+                continue
+            }
+            let mut component = Vec::<String>::new();
+            let mut stack = vec![start];
+            while let Some(index) = stack.pop() {
+                if visited[index.index()] {
+                    continue
+                }
+                visited[index.index()] = true;
+                component.push(self.floors[index].get_data().get_id());
+                stack.extend(self.floors.neighbors(index));
+            }
+            components.push(component);
+        }
+        return components;
+    }
+}
+
+/// A problem in the structure of a building, found by [Building::validate].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationProblem {
+    /// The building consists of more than one connected component. Contains the room ids of every component.
+    DisconnectedComponents(Vec<Vec<String>>),
+    /// There is no start node, so no room can be reached.
+    NoStartNodes,
+    /// The room with this id can not be reached from any start node.
+    UnreachableRoom(String),
+    /// The sub room with the first id has a parent room with the second id, which does not exist.
+    MissingParent(String, String),
+    /// The room with this id has no sensors.
+    RoomWithoutSensors(String),
+}
+
+impl Display for ValidationProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationProblem::DisconnectedComponents(components) => write!(f, "building consists of {} disconnected components: {:?}", components.len(), components),
+            ValidationProblem::NoStartNodes => write!(f, "building has no start nodes"),
+            ValidationProblem::UnreachableRoom(id) => write!(f, "{} can not be reached from any start node", id),
+            ValidationProblem::MissingParent(id, parent_id) => write!(f, "parent {} of sub room {} does not exist", parent_id, id),
+            ValidationProblem::RoomWithoutSensors(id) => write!(f, "{} has no sensors", id),
+        }
+    }
+}
+
+/// Result of [Building::validate], containing all problems that were found.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+    problems: Vec<ValidationProblem>,
+}
+
+impl ValidationReport {
+    /// Returns true if no problems were found.
+    pub fn is_valid(&self) -> bool {
+        return self.problems.is_empty();
+    }
+
+    /// Gets all problems that were found.
+    pub fn get_problems(&self) -> &Vec<ValidationProblem> {
+        return self.problems.borrow();
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.problems.is_empty() {
+            return write!(f, "No problems found");
+        }
+        writeln!(f, "{} problems found:", self.problems.len())?;
+        for problem in &self.problems {
+            writeln!(f, "\t{}", problem)?;
+        }
+        return Ok(());
+    }
+}
+
+impl Building {
+    /// Sets the rooms where individuals enter the building.
+    /// <br/>By default these are all staircases on the ground floor, i.e. all rooms whose id starts with _S_.
+    /// <br/>Returns an error if one of the rooms does not exist.
+    pub fn set_entrances(&mut self, ids: Vec<String>) -> Result<(), BuildingError> {
+        for id in &ids {
+            self.find_node_index(id.as_str())?;
+        }
+        self.entrances = Some(ids);
+        return Ok(());
+    }
+
+    /// Sets the rooms where individuals leave the building.
+    /// <br/>By default these are the [entrances](Building::get_entrances).
+    /// <br/>Returns an error if one of the rooms does not exist.
+    pub fn set_exits(&mut self, ids: Vec<String>) -> Result<(), BuildingError> {
+        for id in &ids {
+            self.find_node_index(id.as_str())?;
+        }
+        self.exits = Some(ids);
+        return Ok(());
+    }
+
+    /// Sets the rooms individuals move to, each with its attractiveness.
+    /// A room with an attractiveness of 2 is chosen twice as often as a room with an attractiveness of 1,
+    /// rooms with an attractiveness of 0 are never chosen.
+    /// <br/>By default these are all [sub rooms](Building::is_sub_room) except lighting zones with an attractiveness of 1.
+    /// <br/>Returns an error if one of the rooms does not exist or if an attractiveness is higher than [MAX_ATTRACTIVENESS].
+    pub fn set_destinations(&mut self, destinations: Vec<(String, u32)>) -> Result<(), BuildingError> {
+        for (id, attractiveness) in &destinations {
+            self.find_node_index(id.as_str())?;
+            check_attractiveness(id.as_str(), *attractiveness)?;
+        }
+        self.destinations = Some(destinations);
+        return Ok(());
+    }
+
+    /// Sets the attractiveness of a single destination, see [Building::set_destinations].
+    /// If the room is not yet a destination, it is added.
+    /// <br/>Returns an error if the room does not exist or if the attractiveness is higher than [MAX_ATTRACTIVENESS].
+    pub fn set_attractiveness(&mut self, id: &str, attractiveness: u32) -> Result<(), BuildingError> {
+        self.find_node_index(id)?;
+        check_attractiveness(id, attractiveness)?;
+        let mut destinations = self.get_destinations();
+        match destinations.iter_mut().find(|(room_id, _)| room_id == id) {
+            Some(destination) => destination.1 = attractiveness,
+            None => destinations.push((id.to_string(), attractiveness)),
+        }
+        self.destinations = Some(destinations);
+        return Ok(());
+    }
+
+    /// Gets the ids of the rooms where individuals enter the building.
+    pub fn get_entrances(&self) -> Vec<String> {
+        return match &self.entrances {
+            Some(entrances) => entrances.clone(),
+            None => {
+                let mut ids = Vec::<String>::new();
+                for index in self.floors.node_indices() {
+                    let id = self.floors[index].get_data().get_id();
+                    if id.starts_with("S") {
+                        ids.push(id);
+                    }
+                }
+                ids
+            }
+        };
+    }
+
+    /// Gets the ids of the rooms where individuals leave the building.
+    pub fn get_exits(&self) -> Vec<String> {
+        return match &self.exits {
+            Some(exits) => exits.clone(),
+            None => self.get_entrances(),
+        };
+    }
+
+    /// Gets the ids of the rooms individuals move to, each with its attractiveness.
+    pub fn get_destinations(&self) -> Vec<(String, u32)> {
+        return match &self.destinations {
+            Some(destinations) => destinations.clone(),
+            None => {
+                let mut destinations = Vec::<(String, u32)>::new();
+                for index in self.floors.node_indices() {
+                    let id = self.floors[index].get_data().get_id();
+                    if self.is_sub_room(id.as_str()) & (self.get_room_kind(id.as_str()) != Some(RoomKind::LightingZone)) {
+                        destinations.push((id, 1));
+                    }
+                }
+                destinations
+            }
+        };
+    }
+
+    /// Gets the entrances, if they were set via [Building::set_entrances].
+    pub(crate) fn get_explicit_entrances(&self) -> Option<&Vec<String>> {
+        return self.entrances.as_ref();
+    }
+
+    /// Gets the exits, if they were set via [Building::set_exits].
+    pub(crate) fn get_explicit_exits(&self) -> Option<&Vec<String>> {
+        return self.exits.as_ref();
+    }
+
+    /// Gets the destinations, if they were set via [Building::set_destinations] or [Building::set_attractiveness].
+    pub(crate) fn get_explicit_destinations(&self) -> Option<&Vec<(String, u32)>> {
+        return self.destinations.as_ref();
+    }
+
+    fn get_node_indices_of(&self, ids: Vec<String>) -> Vec<NodeIndex> {
+        return ids.iter().filter_map(|id| self.node_index(id.as_str())).collect();
+    }
+}
+
+fn check_attractiveness(id: &str, attractiveness: u32) -> Result<(), BuildingError> {
+    if attractiveness > MAX_ATTRACTIVENESS {
+        return Err(BuildingError::AttractivenessTooHigh(id.to_string(), attractiveness));
+    }
+    return Ok(());
+}
+
+impl UnderlyingStructure for Building {
+    fn get_graph_structure(&self) -> &Graph<Node<Arc<dyn NodeData>>, Edge<Arc<dyn EdgeData>>, Undirected> {
+        return self.get_floors();
+    }
+
+    /// Gets the nodes of the [entrances](Building::get_entrances).
+    fn get_start_nodes(&self) -> Vec<NodeIndex> {
+        return self.get_node_indices_of(self.get_entrances());
+    }
+
+    /// Gets the nodes of the [exits](Building::get_exits).
+    fn get_end_nodes(&self) -> Vec<NodeIndex> {
+        return self.get_node_indices_of(self.get_exits());
+    }
+
+    /// Gets the nodes of the [destinations](Building::get_destinations). Every node is contained
+    /// as often as its attractiveness, so that the more attractive destinations are chosen more often.
+    /// <br/>Because of this the attractiveness is limited to [MAX_ATTRACTIVENESS].
+    fn get_nodes_to_move_to(&self) -> Vec<NodeIndex> {
+        let mut res = Vec::new();
+        for (id, attractiveness) in self.get_destinations() {
+            if let Some(index) = self.node_index(id.as_str()) {
+                for _ in 0..attractiveness {
+                    res.push(index);
+                }
+            }
+        }
+        return res;
+    }
+}
+
+impl Building {
+    /// Adds a room with a specific id and its sensors as they are, e.g. when a saved building is loaded.
+    /// <br/>The parent does not need to exist, see [Building::update_children].
+    pub(crate) fn insert_room(&mut self, id: String, kind: RoomKind, level: i32, windows: bool, parent: Option<String>, sensors: Vec<Sensor>) -> Result<(), BuildingError> {
+        if !self.levels.iter().any(|floor| floor.get_level() == level) {
+            return Err(BuildingError::UnknownFloor(level));
+        }
+        let mut node = Node::new(create_node_data(kind, id, windows));
+        node.add_sensors(sensors);
+        self.add_room_node(node, level, kind, windows, parent)?;
+        return Ok(());
+    }
+
+    /// Connects two rooms with an edge of a specific kind and id, e.g. when a saved building is loaded.
+    /// <br/>The travel time is only used for connections between floors.
+    pub(crate) fn insert_connection(&mut self, id_room_1: &str, id_room_2: &str, connection_type: ConnectionType, edge_id: String, travel_time: Option<Duration>) -> Result<(), BuildingError> {
+        self.new_connection(id_room_1, id_room_2, create_edge_data(connection_type, edge_id))?;
+        if let Some(travel_time) = travel_time {
+            self.travel_times.insert(connection_key(id_room_1, id_room_2), travel_time);
+        }
+        return Ok(());
+    }
+
+    /// Adds the desks of a lighting zone, whose node already exists, e.g. when a saved building is loaded.
+    /// <br/>Returns an error if the node of the zone is no [lighting zone](RoomKind::LightingZone) or if one of the desks does not exist.
+    pub(crate) fn insert_lighting_zone(&mut self, id: String, desks: Vec<String>) -> Result<(), BuildingError> {
+        if (self.get_room_kind(id.as_str()) != Some(RoomKind::LightingZone)) | self.get_lighting_zone(id.as_str()).is_some() {
+            return Err(BuildingError::UnknownRoomId(id));
+        }
+        for desk in &desks {
+            self.find_node_index(desk)?;
+        }
+        self.lighting_zones.push(LightingZone { id, desks });
+        return Ok(());
+    }
+
+    /// Sets the sub rooms of every room to the rooms that have it as parent, in the order of the graph.
+    /// <br/>This is needed if rooms were inserted before their parent.
+    pub(crate) fn update_children(&mut self) {
+        let mut children = HashMap::<String, Vec<String>>::new();
+        for index in self.floors.node_indices() {
+            let id = self.floors[index].get_data().get_id();
+            if let Some(parent_id) = self.parent(id.as_str()) {
+                children.entry(parent_id).or_default().push(id);
+            }
+        }
+        for (id, info) in self.room_infos.iter_mut() {
+            info.children = children.remove(id).unwrap_or_default();
+        }
+    }
+}
+
+/// Physical properties of a room, that are needed e.g. for lighting, daylight or thermal models.
+/// <br/>The [type](RoomType) of the room is not part of its properties, see [Building::set_room_type].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoomProperties {
+    /// Floor area in m².
+    pub floor_area: f64,
+    /// Ceiling height in m.
+    pub ceiling_height: f64,
+    /// Volume in m³, None if it is the product of floor area and ceiling height, see [RoomProperties::get_volume].
+    #[serde(default)]
+    pub volume: Option<f64>,
+    /// Area of all windows in m², the room [has windows](Building::has_windows) if it is greater than 0.
+    #[serde(default)]
+    pub window_area: f64,
+    /// Orientation of the facade with the windows, None if the room has no facade.
+    #[serde(default)]
+    pub orientation: Option<Orientation>,
+}
+
+impl RoomProperties {
+    /// Creates the properties of a room without windows. The volume is the product of floor area and ceiling height.
+    pub fn new(floor_area: f64, ceiling_height: f64) -> Self {
+        RoomProperties {
+            floor_area,
+            ceiling_height,
+            volume: None,
+            window_area: 0.0,
+            orientation: None,
+        }
+    }
+
+    /// Sets the area of the windows and the orientation of the facade.
+    pub fn with_windows(mut self, window_area: f64, orientation: Orientation) -> Self {
+        self.window_area = window_area;
+        self.orientation = Some(orientation);
+        return self;
+    }
+
+    /// Sets the volume, if the room is not a cuboid.
+    pub fn with_volume(mut self, volume: f64) -> Self {
+        self.volume = Some(volume);
+        return self;
+    }
+
+    /// Gets the volume in m³, which is the product of floor area and ceiling height if it was not set explicitly.
+    pub fn get_volume(&self) -> f64 {
+        return self.volume.unwrap_or(self.floor_area * self.ceiling_height);
+    }
+}
+
+/// Coordinates of a room in m, e.g. of its center. For a 2D floor plan `z` can be 0.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Coordinates {
+    pub x: f64,
+    pub y: f64,
+    #[serde(default)]
+    pub z: f64,
+}
+
+impl Coordinates {
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        return Coordinates { x, y, z }
+    }
+
+    /// Gets the euclidean distance in m to other coordinates.
+    pub fn distance(&self, other: &Coordinates) -> f64 {
+        return ((self.x - other.x).powi(2) + (self.y - other.y).powi(2) + (self.z - other.z).powi(2)).sqrt();
+    }
+}
+
+/// Orientation of a facade, i.e. the direction the windows are facing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Orientation {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+/// Usage of a room.
+/// <br/>Every type has a [default profile](RoomType::get_default_profile) with the power of the lights,
+/// the expected occupancy and the policy for the rules of the room.
+/// The policy can be changed per type or per room, see [Building::get_rule_policy].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RoomType {
+    Office,
+    MeetingRoom,
+    Corridor,
+    Kitchen,
+    Restroom,
+    Storage,
+    Lobby,
+    Staircase,
+    /// Desk in an open-plan area, see [Building::add_desk_grid].
+    /// Its lights are the ones of its [lighting zone](LightingZone).
+    Desk,
+    #[default]
+    Other,
+}
+
+impl RoomType {
+    /// Gets all room types.
+    pub fn get_all() -> Vec<RoomType> {
+        return vec![RoomType::Office, RoomType::MeetingRoom, RoomType::Corridor, RoomType::Kitchen, RoomType::Restroom,
+                    RoomType::Storage, RoomType::Lobby, RoomType::Staircase, RoomType::Desk, RoomType::Other];
+    }
+
+    /// Gets the default profile of the room type.
+    /// <br/>Offices use the values of the sub rooms and corridors the values of the other rooms of the building in _main.rs_.
+    pub fn get_default_profile(&self) -> RoomTypeProfile {
+        return match self {
+            RoomType::Office => RoomTypeProfile::new([406.0, 0.0, 0.0], 0.6, RulePolicy::SwitchOff),
+            RoomType::MeetingRoom => RoomTypeProfile::new([580.0, 0.0, 0.0], 0.3, RulePolicy::SwitchOff),
+            RoomType::Corridor => RoomTypeProfile::new([1740.0, 870.0, 0.0], 0.2, RulePolicy::DimOrSwitchOff),
+            RoomType::Kitchen => RoomTypeProfile::new([348.0, 0.0, 0.0], 0.2, RulePolicy::SwitchOff),
+            RoomType::Restroom => RoomTypeProfile::new([174.0, 0.0, 0.0], 0.1, RulePolicy::SwitchOff),
+            RoomType::Storage => RoomTypeProfile::new([116.0, 0.0, 0.0], 0.02, RulePolicy::SwitchOff),
+            RoomType::Lobby => RoomTypeProfile::new([1160.0, 580.0, 0.0], 0.3, RulePolicy::DimOrSwitchOff),
+            RoomType::Staircase => RoomTypeProfile::new([290.0, 145.0, 0.0], 0.05, RulePolicy::DimOrSwitchOff),
+            RoomType::Desk => RoomTypeProfile::new([150.0, 0.0, 0.0], 0.5, RulePolicy::SwitchOff),
+            RoomType::Other => RoomTypeProfile::new([1740.0, 870.0, 0.0], 0.2, RulePolicy::DimOrSwitchOff),
+        };
+    }
+}
+
+/// Default values of a [RoomType].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoomTypeProfile {
+    /// Power of the lights in W, when they are on, dimmed and off.
+    pub lighting_power: [f64; 3],
+    /// Expected share of the working hours in which the room is occupied, between 0 and 1.
+    pub expected_occupancy: f64,
+    /// Policy for the rules of the lights in the room.
+    pub rule_policy: RulePolicy,
+}
+
+impl RoomTypeProfile {
+    pub fn new(lighting_power: [f64; 3], expected_occupancy: f64, rule_policy: RulePolicy) -> Self {
+        return RoomTypeProfile {
+            lighting_power,
+            expected_occupancy,
+            rule_policy,
+        }
+    }
+}
+
+/// Policy for the rules of the lights in a room.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RulePolicy {
+    /// The lights are turned off when nobody is in the room anymore and
+    /// turned on when someone arrives outside of daylight hours.
+    SwitchOff,
+    /// The lights are dimmed during the day or turned off at night when nobody is in the room anymore and
+    /// turned on whenever someone arrives.
+    DimOrSwitchOff,
+    /// No rules are created for the room.
+    Manual,
+}
+
+/// Kind of a room in the building.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoomKind {
+    Staircase,
+    Elevator,
+    RoomWithDoors,
+    RoomWithoutDoors,
+    /// Node with the lights of a [lighting zone](LightingZone), that nobody moves to.
+    LightingZone,
+}
+
+/// Group of desks that share their lights, e.g. a luminaire that spans several desks of an
+/// [open-plan area](Building::add_desk_grid).
+/// <br/>The sensors of the lights are the ones of the node of the zone, see [Building::add_lighting_zone].
+#[derive(Debug, Clone)]
+pub struct LightingZone {
+    id: String,
+    desks: Vec<String>,
+}
+
+impl LightingZone {
+    pub fn get_id(&self) -> String {
+        return self.id.clone();
+    }
+
+    /// Gets the ids of the desks that share the lights of the zone.
+    pub fn get_desks(&self) -> &Vec<String> {
+        return &self.desks;
+    }
+}
+
+/// Information about a room, that is kept next to the graph.
+struct RoomInfo {
+    floor: i32,
+    kind: RoomKind,
+    windows: bool,
+    properties: Option<RoomProperties>,
+    parent: Option<String>,
+    children: Vec<String>,
+    room_type: Option<RoomType>,
+    rule_policy: Option<RulePolicy>,
+    coordinates: Option<Coordinates>,
+}
+
+/// Kind of a sensor at a door.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoorSensorKind {
+    /// Door contact, that reports that the door was opened and closed again.
+    Contact,
+    /// Bidirectional people counter, that reports whether someone entered or left the room with this id.
+    PeopleCounter(String),
+}
+
+/// State of a door.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoorState {
+    /// The door stays open, so its [contacts](DoorSensorKind::Contact) do not report anything when someone passes it.
+    Open,
+    /// The door is closed, but everyone can open it.
+    Closed,
+    /// The door is locked, movable objects can not pass it.
+    Locked,
+}
+
+/// Period of the day, in which a door is in a specific state.
+/// <br/>If the end is before the start, the period lasts over midnight, e.g. from 20:00 to 06:00.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DoorPeriod {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    pub state: DoorState,
+}
+
+impl DoorPeriod {
+    pub fn new(start: NaiveTime, end: NaiveTime, state: DoorState) -> Self {
+        return DoorPeriod {
+            start,
+            end,
+            state,
+        }
+    }
+
+    /// Checks whether a time lies within the period, including the start but not the end.
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            return (self.start <= time) & (time < self.end);
+        }
+        return (self.start <= time) | (time < self.end);
+    }
+}
+
+/// States of a door over the day: the door is in its default state, unless the time lies within one of its periods.
+/// If periods overlap, the period that was added last applies.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoorSchedule {
+    state: DoorState,
+    #[serde(default)]
+    periods: Vec<DoorPeriod>,
+}
+
+impl DoorSchedule {
+    /// Creates a schedule of a door, which is always in the given state.
+    pub fn new(state: DoorState) -> Self {
+        return DoorSchedule {
+            state,
+            periods: Vec::new(),
+        }
+    }
+
+    /// Gets the state of the door outside of its periods.
+    pub fn get_default_state(&self) -> DoorState {
+        return self.state;
+    }
+
+    pub fn get_periods(&self) -> &Vec<DoorPeriod> {
+        return &self.periods;
+    }
+
+    pub fn add_period(&mut self, period: DoorPeriod) {
+        self.periods.push(period);
+    }
+
+    /// Gets the state of the door at a specific time.
+    pub fn get_state(&self, time: NaiveTime) -> DoorState {
+        return self.periods.iter().rev().find(|period| period.contains(time))
+            .map(|period| period.state)
+            .unwrap_or(self.state);
+    }
+}
+
+impl Default for DoorSchedule {
+    /// A door is closed all day by default.
+    fn default() -> Self {
+        return DoorSchedule::new(DoorState::Closed);
+    }
+}
+
+/// Kind of a connection between two rooms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionType {
+    Door,
+    NoDoor,
+    /// Stairs between two staircases on adjacent floors.
+    Stairs,
+    /// Elevator shaft between two elevators on adjacent floors.
+    Shaft,
+}
+
+impl ConnectionType {
+    /// Gets the kind of a connection by the id of its edge data, e.g. [ConnectionType::Door] for _Door3_sub_.
+    pub fn from_edge_id(edge_id: &str) -> Option<ConnectionType> {
+        // NoDoor has to be checked before Door
+        if edge_id.starts_with("NoDoor") {
+            return Some(ConnectionType::NoDoor);
+        }
+        if edge_id.starts_with("Door") {
+            return Some(ConnectionType::Door);
+        }
+        if edge_id.starts_with("Stairs") {
+            return Some(ConnectionType::Stairs);
+        }
+        if edge_id.starts_with("Shaft") {
+            return Some(ConnectionType::Shaft);
+        }
+        return None;
+    }
+}
+
+/// Creates the data of an edge of the given kind with a specific id.
+fn create_edge_data(connection_type: ConnectionType, id: String) -> Arc<dyn EdgeData> {
+    return match connection_type {
+        ConnectionType::Door => Arc::new(Door { id }),
+        ConnectionType::NoDoor => Arc::new(NoDoor { id }),
+        ConnectionType::Stairs => Arc::new(Stairs { id }),
+        ConnectionType::Shaft => Arc::new(Shaft { id }),
+    };
+}
+
+/// Creates the data of a node of the given kind with a specific id.
+fn create_node_data(kind: RoomKind, id: String, windows: bool) -> Arc<dyn NodeData> {
+    return match kind {
+        RoomKind::Staircase => Arc::new(Staircase { id }),
+        RoomKind::Elevator => Arc::new(Elevator { id }),
+        RoomKind::RoomWithDoors => Arc::new(RoomWithDoors { id, windows }),
+        RoomKind::RoomWithoutDoors => Arc::new(RoomWithoutDoors { id, windows }),
+        RoomKind::LightingZone => Arc::new(LightingZoneNode { id }),
+    };
+}
+
+/// Gets the name of a door, that is used in the ids of its sensors instead of the id of a room.
+fn door_sensor_room_id(id_room_1: &str, id_room_2: &str) -> String {
+    let (id_room_1, id_room_2) = connection_key(id_room_1, id_room_2);
+    return "Door_".to_owned() + id_room_1.as_str() + "_" + id_room_2.as_str();
+}
+
+/// Creates the id of a sensor in a room. The number is the position of the sensor in the room.
+fn sensor_id(room_id: &str, number: usize, sensor_type_id: &str) -> String {
+    return SensorId::new(room_id, number, sensor_type_id).to_string();
+}
+
+/// Replaces the id `old` with the id `new` in the keys of a map of connections, e.g. of the travel times.
+fn rename_in_connections<T>(connections: &mut HashMap<(String, String), T>, old: &str, new: &str) {
+    let keys: Vec<(String, String)> = connections.keys()
+        .filter(|(id_room_1, id_room_2)| (id_room_1 == old) | (id_room_2 == old))
+        .cloned().collect();
+    for (id_room_1, id_room_2) in keys {
+        let value = connections.remove(&(id_room_1.clone(), id_room_2.clone())).unwrap();
+        let other = if id_room_1 == old { id_room_2 } else { id_room_1 };
+        connections.insert(connection_key(new, other.as_str()), value);
+    }
+}
+
+/// Key of a connection between two rooms, independent of the order of the rooms.
+fn connection_key(id_room_1: &str, id_room_2: &str) -> (String, String) {
+    if id_room_1 <= id_room_2 {
+        return (id_room_1.to_string(), id_room_2.to_string());
+    }
+    return (id_room_2.to_string(), id_room_1.to_string());
+}
+
+/// Prefix of the ids of the rooms on a floor. The ground floor has no prefix.
+fn floor_prefix(level: i32) -> String {
+    if level == 0 {
+        return "".to_string();
+    }
+    return Floor::new(level).get_id() + "_";
+}
+
+/// Representation of a floor of the building. The ground floor has the level 0,
+/// basements have negative levels.
+pub struct Floor {
+    id: String,
+    level: i32,
+}
+
+impl Floor {
+    fn new(level: i32) -> Self {
+        Floor {
+            id: "F".to_owned() + level.to_string().as_str(),
+            level,
+        }
+    }
+
+    pub fn get_id(&self) -> String {
+        return self.id.clone();
+    }
+
+    pub fn get_level(&self) -> i32 {
+        return self.level;
+    }
+}
+
+
+struct Staircase {
+    id: String
+}
+
+impl Staircase {
+    fn new(id_count: i32, floor_prefix: &str) -> Self {
+        Staircase {
+            id: floor_prefix.to_owned() + "S" + id_count.to_string().as_str(),
+        }
+    }
+}
+
+impl NodeData for Staircase {
+    fn get_id(&self) -> String {
+        return self.id.clone();
+    }
+}
+
+
+struct Elevator {
+    id: String
+}
+
+impl Elevator {
+    fn new(id_count: i32, floor_prefix: &str) -> Self {
+        Elevator {
+            id: floor_prefix.to_owned() + "E" + id_count.to_string().as_str(),
+        }
+    }
+}
+
+impl NodeData for Elevator {
+    fn get_id(&self) -> String {
+        return self.id.clone();
+    }
+}
+
+
+struct RoomWithDoors {
+    id: String,
+    windows: bool,
+}
+
+impl RoomWithDoors {
+    /// If `sub_room` is false, `prefix` is the prefix of the floor, otherwise it is the id of the parent room.
+    fn new(id_count: i32, sub_room: bool, prefix: String, windows: bool) -> Self {
+        let sub_room_flag = "_sub";
+        let mut id = "RwD".to_owned() + id_count.to_string().as_str();
+        if sub_room {
+            id = prefix + "_" +  id.as_str() + sub_room_flag;
+        } else {
+            id = prefix + id.as_str();
+        }
+        RoomWithDoors{id, windows}
+
+    }
+
+    pub fn has_windows(&self) -> bool {
+        self.windows
+    }
+}
+
+impl NodeData for RoomWithDoors {
+    fn get_id(&self) -> String {
+        return self.id.clone();
+    }
+}
+
+
+struct RoomWithoutDoors {
+    id: String,
+    windows: bool,
+}
+
+impl RoomWithoutDoors {
+    /// If `sub_room` is false, `prefix` is the prefix of the floor, otherwise it is the id of the parent room.
+    fn new(id_count: i32, sub_room: bool, prefix: String, windows: bool) -> Self {
+        let sub_room_flag = "_sub";
+        let mut id = "RwnD".to_owned() + id_count.to_string().as_str();
+        if sub_room {
+            id = prefix + "_" + id.as_str() + sub_room_flag;
+        } else {
+            id = prefix + id.as_str();
+        }
+        RoomWithoutDoors {
+            id,
+            windows
+        }
+    }
+
+    pub fn has_windows(&self) -> bool {
+        self.windows
+    }
+}
+
+impl NodeData for RoomWithoutDoors {
+    fn get_id(&self) -> String {
+        return self.id.clone();
+    }
+}
+
+
+struct LightingZoneNode {
+    id: String,
+}
+
+impl NodeData for LightingZoneNode {
+    fn get_id(&self) -> String {
+        return self.id.clone();
+    }
+}
+
+struct Door {
+    id: String,
     //number_of_doors: i32
 }
 
@@ -444,4 +1990,87 @@ impl EdgeData for NoDoor {
     fn get_id(&self) -> String {
         return self.id.clone();
     }
-}
\ No newline at end of file
+}
+
+struct Stairs {
+    id: String
+}
+
+impl Stairs {
+    fn new(id_count: i32) -> Self {
+        Stairs {
+            id: "Stairs".to_owned() + id_count.to_string().as_str(),
+        }
+    }
+}
+
+impl EdgeData for Stairs {
+    fn get_id(&self) -> String {
+        return self.id.clone();
+    }
+}
+
+struct Shaft {
+    id: String
+}
+
+impl Shaft {
+    fn new(id_count: i32) -> Self {
+        Shaft {
+            id: "Shaft".to_owned() + id_count.to_string().as_str(),
+        }
+    }
+}
+
+impl EdgeData for Shaft {
+    fn get_id(&self) -> String {
+        return self.id.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::sensor_types;
+    use super::*;
+
+    #[test]
+    fn connecting_rooms_twice_fails() {
+        let mut building = Building::new();
+        building.add_room_without_doors(0, Vec::new(), false, -1).unwrap();
+        building.add_sub_rooms_with_doors(14, "RwnD0".to_string(), Vec::new(), true, -1).unwrap();
+        building.new_door_connection("RwnD0_RwD10_sub".to_string(), "RwnD0_RwD11_sub".to_string(), 0).unwrap();
+        let result = building.new_door_connection("RwnD0_RwD10_sub".to_string(), "RwnD0_RwD11_sub".to_string(), 1);
+        assert_eq!(result, Err(BuildingError::DuplicateConnection("RwnD0_RwD10_sub".to_string(), "RwnD0_RwD11_sub".to_string())));
+        assert_eq!(building.get_floors().edge_count(), 15);
+    }
+
+    #[test]
+    fn invalid_lengths_and_coordinates_are_rejected() {
+        let mut building = Building::new();
+        building.add_room_without_doors(0, Vec::new(), false, -1).unwrap();
+        building.add_room_without_doors(1, Vec::new(), false, -1).unwrap();
+        building.new_no_door_connection("RwnD0".to_string(), "RwnD1".to_string(), 0).unwrap();
+        for length in [-1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(building.set_connection_length("RwnD0", "RwnD1", length), Err(BuildingError::InvalidLength("RwnD0".to_string(), "RwnD1".to_string())));
+        }
+        building.set_connection_length("RwnD0", "RwnD1", 0.0).unwrap();
+        assert_eq!(building.get_connection_length("RwnD0", "RwnD1"), Some(0.0));
+        assert_eq!(building.set_coordinates("RwnD0", Coordinates::new(f64::NAN, 0.0, 0.0)), Err(BuildingError::InvalidCoordinates("RwnD0".to_string())));
+        building.set_coordinates("RwnD0", Coordinates::new(-3.0, 0.0, 0.0)).unwrap();
+    }
+
+    #[test]
+    fn renaming_keeps_sensor_numbers() {
+        let sensor_types = sensor_types();
+        let mut building = Building::new();
+        building.add_room_without_doors(0, vec![(1, sensor_types[0].clone()), (2, sensor_types[1].clone())], false, -1).unwrap();
+        let sensor_ids = |building: &Building, id: &str| -> Vec<String> {
+            return building.get_floors()[building.node_index(id).unwrap()].get_sensors().iter().map(|sensor| sensor.get_id()).collect();
+        };
+        let type_0 = sensor_types[0].get_id();
+        let type_1 = sensor_types[1].get_id();
+        assert_eq!(sensor_ids(&building, "RwnD0"), vec![sensor_id("RwnD0", 0, &type_0), sensor_id("RwnD0", 0, &type_1), sensor_id("RwnD0", 1, &type_1)]);
+        building.rename_room("RwnD0", "Kitchen").unwrap();
+        assert_eq!(sensor_ids(&building, "Kitchen"), vec![sensor_id("Kitchen", 0, &type_0), sensor_id("Kitchen", 0, &type_1), sensor_id("Kitchen", 1, &type_1)]);
+    }
+}
@@ -0,0 +1,156 @@
+use chrono::{Duration, NaiveTime};
+use rand::Rng;
+use crate::human::TimeDistribution;
+use crate::room::{Building, RoomType};
+
+/// Lunch break in the nearest kitchen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lunch {
+    pub start: TimeDistribution,
+    pub duration: Duration,
+}
+
+/// Meeting, which the movable objects attend with a specific probability.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Meeting {
+    pub start: NaiveTime,
+    pub duration: Duration,
+    /// Probability that a movable object attends the meeting, between 0 and 1. Other values are clamped and NaN is treated as 0.
+    pub attendance: f64,
+    /// Id of the room of the meeting, if None a random room of type [RoomType::MeetingRoom] is used.
+    pub room: Option<String>,
+}
+
+/// Daily schedule of a group of movable objects, see [Individual::set_schedule](crate::human::Individual::set_schedule).
+/// <br/>A movable object arrives, goes to its desk and stays there, except for coffee trips to the nearest kitchen,
+/// trips to the nearest restroom, its lunch break and the meetings it attends. Then it leaves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schedule {
+    /// Number of coffee trips per hour at the desk.
+    pub coffee_trips_per_hour: f64,
+    /// Number of restroom trips per hour at the desk.
+    pub restroom_trips_per_hour: f64,
+    /// Time spent in the kitchen or restroom during a trip.
+    pub trip_duration: Duration,
+    pub lunch: Option<Lunch>,
+    pub meetings: Vec<Meeting>,
+}
+
+impl Schedule {
+    /// Creates a schedule with a coffee trip every two hours, a restroom trip every three hours, both taking 5 minutes,
+    /// a lunch break of 30 minutes starting around 12:00 and no meetings.
+    pub fn new() -> Self {
+        return Schedule {
+            coffee_trips_per_hour: 0.5,
+            restroom_trips_per_hour: 1.0 / 3.0,
+            trip_duration: Duration::minutes(5),
+            lunch: Some(Lunch {
+                start: TimeDistribution::Normal { mean: NaiveTime::from_hms_opt(12, 0, 0).unwrap(), standard_deviation: Duration::minutes(20) },
+                duration: Duration::minutes(30),
+            }),
+            meetings: Vec::new(),
+        }
+    }
+
+    /// Creates the rooms a movable object visits during the day and the times it leaves for them,
+    /// as needed by [create_trajectory](crate::movement::create_trajectory), starting with the desk at the time of arrival.
+    /// Leaving the building is not included.
+    /// <br/>Lunch and meetings take precedence over trips: activities that overlap a preceding one, that do not lie
+    /// between arrival and departure or whose room does not exist are left out.
+    pub fn create_visits<R: Rng>(&self, building: &Building, desk: &str, arrival: NaiveTime, departure: NaiveTime, rng: &mut R) -> Vec<(String, NaiveTime)> {
+        // start, duration and room of every activity away from the desk, lunch and meetings first
+        let mut activities = Vec::<(NaiveTime, Duration, String)>::new();
+        if let Some(lunch) = &self.lunch {
+            if let Some(kitchen) = nearest_room_of_type(building, desk, RoomType::Kitchen) {
+                activities.push((lunch.start.sample(rng), lunch.duration, kitchen));
+            }
+        }
+        let meeting_rooms = rooms_of_type(building, RoomType::MeetingRoom);
+        for meeting in &self.meetings {
+            // clamp keeps NaN, which gen_bool does not accept
+            let attendance = if meeting.attendance.is_nan() { 0.0 } else { meeting.attendance.clamp(0.0, 1.0) };
+            if !rng.gen_bool(attendance) {
+                continue
+            }
+            let room = match &meeting.room {
+                Some(room) => Some(room.clone()),
+                None if !meeting_rooms.is_empty() => Some(meeting_rooms[rng.gen_range(0..meeting_rooms.len())].clone()),
+                None => None
+            };
+            if let Some(room) = room {
+                activities.push((meeting.start, meeting.duration, room));
+            }
+        }
+        activities.sort_by(|(start_1, _, _), (start_2, _, _)| start_1.cmp(start_2));
+        for (trips_per_hour, room_type) in [(self.coffee_trips_per_hour, RoomType::Kitchen), (self.restroom_trips_per_hour, RoomType::Restroom)] {
+            let room = match nearest_room_of_type(building, desk, room_type) {
+                Some(room) => room,
+                None => continue
+            };
+            if trips_per_hour.is_nan() | (trips_per_hour <= 0.0) {
+                continue
+            }
+            // the trips are a poisson process, so the times between them are exponentially distributed
+            let mut time = arrival;
+            loop {
+                let hours = -(1.0 - rng.gen::<f64>()).ln() / trips_per_hour;
+                let gap = Duration::milliseconds((hours * 3_600_000.0) as i64);
+                if gap >= departure - time {
+                    break
+                }
+                time = time + gap;
+                activities.push((time, self.trip_duration, room.clone()));
+            }
+        }
+
+        // trips are only made when they do not overlap lunch or a meeting
+        let mut accepted = Vec::<(NaiveTime, NaiveTime, String)>::new();
+        for (start, duration, room) in activities {
+            let end = start + duration;
+            if (start < arrival) | (end > departure) | (end < start) | building.node_index(room.as_str()).is_none() {
+                continue
+            }
+            if accepted.iter().any(|(other_start, other_end, _)| (start < *other_end) & (*other_start < end)) {
+                continue
+            }
+            accepted.push((start, end, room));
+        }
+        accepted.sort_by(|(start_1, _, _), (start_2, _, _)| start_1.cmp(start_2));
+
+        let mut visits = vec![(desk.to_string(), arrival)];
+        for (start, end, room) in accepted {
+            visits.push((room, start));
+            visits.push((desk.to_string(), end));
+        }
+        return visits;
+    }
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        return Schedule::new();
+    }
+}
+
+/// Gets the ids of all rooms of a specific type in the order of the graph.
+fn rooms_of_type(building: &Building, room_type: RoomType) -> Vec<String> {
+    let graph = building.get_floors();
+    return graph.node_indices()
+        .map(|index| graph[index].get_data().get_id())
+        .filter(|id| building.get_room_type(id.as_str()) == Some(room_type))
+        .collect();
+}
+
+/// Gets the id of the room of a specific type, that is nearest to a room, see [Building::distance].
+/// <br/>Returns None if no room of the type can be reached.
+fn nearest_room_of_type(building: &Building, from: &str, room_type: RoomType) -> Option<String> {
+    let mut nearest: Option<(f64, String)> = None;
+    for id in rooms_of_type(building, room_type) {
+        if let Ok(Some(distance)) = building.distance(from, id.as_str()) {
+            if nearest.as_ref().map_or(true, |(nearest_distance, _)| distance < *nearest_distance) {
+                nearest = Some((distance, id));
+            }
+        }
+    }
+    return nearest.map(|(_, id)| id);
+}
@@ -0,0 +1,481 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use chrono::Duration;
+use petgraph::graph::NodeIndex;
+use serde::Deserialize;
+use rubalosim::sensor::SensorType;
+use crate::room::{Building, BuildingError, RoomProperties, RoomType, Coordinates, DoorSchedule};
+
+/// Specification of a whole building, as it is read from a JSON or TOML file.
+/// <br/>The rooms are created first in the given order, then the sub rooms and at last the connections between floors.
+/// Every connection is created in the given order as soon as both of its rooms exist, so that connections between
+/// sub rooms can be specified as well and the graph is the same as if the building was created step by step.
+#[derive(Deserialize, Debug, Default)]
+pub struct BuildingSpecification {
+    #[serde(default)]
+    pub rooms: Vec<RoomSpecification>,
+    #[serde(default)]
+    pub sub_rooms: Vec<SubRoomSpecification>,
+    #[serde(default)]
+    pub connections: Vec<ConnectionSpecification>,
+    #[serde(default)]
+    pub floor_connections: Vec<FloorConnectionSpecification>,
+    /// Ids of the rooms where individuals enter the building, if empty the [default](Building::get_entrances) is used.
+    #[serde(default)]
+    pub entrances: Vec<String>,
+    /// Ids of the rooms where individuals leave the building, if empty the [default](Building::get_exits) is used.
+    #[serde(default)]
+    pub exits: Vec<String>,
+    /// Rooms individuals move to, if empty the [default](Building::get_destinations) is used.
+    #[serde(default)]
+    pub destinations: Vec<DestinationSpecification>,
+}
+
+/// Specification of a single room. The `kind` field decides if a staircase, an elevator,
+/// a room with doors or a room without doors is created.
+/// <br/>The `floor` field is the level of the floor of the room, the ground floor (0) is the default.
+/// Rooms can have optional [properties](RoomProperties), a [type](RoomType) and [coordinates](Coordinates).
+/// If properties are given, the `windows` field is replaced by whether their window area is greater than 0.
+#[derive(Deserialize, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RoomSpecification {
+    Staircase {
+        id: i32,
+        #[serde(default)]
+        floor: i32,
+        #[serde(default)]
+        coordinates: Option<Coordinates>,
+    },
+    Elevator {
+        id: i32,
+        #[serde(default)]
+        floor: i32,
+        #[serde(default)]
+        coordinates: Option<Coordinates>,
+    },
+    RoomWithDoors {
+        id: i32,
+        #[serde(default)]
+        floor: i32,
+        #[serde(default)]
+        windows: bool,
+        #[serde(default)]
+        sensors: Vec<SensorSpecification>,
+        #[serde(default)]
+        properties: Option<RoomProperties>,
+        #[serde(default)]
+        room_type: Option<RoomType>,
+        #[serde(default)]
+        coordinates: Option<Coordinates>,
+    },
+    RoomWithoutDoors {
+        id: i32,
+        #[serde(default)]
+        floor: i32,
+        #[serde(default)]
+        windows: bool,
+        #[serde(default)]
+        sensors: Vec<SensorSpecification>,
+        #[serde(default)]
+        properties: Option<RoomProperties>,
+        #[serde(default)]
+        room_type: Option<RoomType>,
+        #[serde(default)]
+        coordinates: Option<Coordinates>,
+    },
+}
+
+impl RoomSpecification {
+    fn get_properties(&self) -> Option<&RoomProperties> {
+        return match self {
+            RoomSpecification::RoomWithDoors { properties, .. } => properties.as_ref(),
+            RoomSpecification::RoomWithoutDoors { properties, .. } => properties.as_ref(),
+            _ => None,
+        };
+    }
+
+    fn get_room_type(&self) -> Option<RoomType> {
+        return match self {
+            RoomSpecification::RoomWithDoors { room_type, .. } => *room_type,
+            RoomSpecification::RoomWithoutDoors { room_type, .. } => *room_type,
+            _ => None,
+        };
+    }
+
+    fn get_coordinates(&self) -> Option<Coordinates> {
+        return match self {
+            RoomSpecification::Staircase { coordinates, .. } => *coordinates,
+            RoomSpecification::Elevator { coordinates, .. } => *coordinates,
+            RoomSpecification::RoomWithDoors { coordinates, .. } => *coordinates,
+            RoomSpecification::RoomWithoutDoors { coordinates, .. } => *coordinates,
+        };
+    }
+
+    fn get_floor(&self) -> i32 {
+        return match self {
+            RoomSpecification::Staircase { floor, .. } => *floor,
+            RoomSpecification::Elevator { floor, .. } => *floor,
+            RoomSpecification::RoomWithDoors { floor, .. } => *floor,
+            RoomSpecification::RoomWithoutDoors { floor, .. } => *floor,
+        };
+    }
+}
+
+/// Specification of a number of sub rooms, that are added to the room with the id `parent`.
+/// <br/>If `properties` or a `room_type` are given, every sub room gets these properties or this type and the `windows` field is replaced by whether their window area is greater than 0.
+/// `coordinates` are given to the sub rooms in the order of their creation.
+/// <br/>If `doors` is true (default) the sub rooms are [rooms with doors](crate::room::Building::add_sub_rooms_with_doors),
+/// otherwise [rooms without doors](crate::room::Building::add_sub_rooms_without_doors).
+#[derive(Deserialize, Debug)]
+pub struct SubRoomSpecification {
+    pub parent: String,
+    pub number: i32,
+    #[serde(default = "default_doors")]
+    pub doors: bool,
+    #[serde(default)]
+    pub windows: bool,
+    #[serde(default)]
+    pub sensors: Vec<SensorSpecification>,
+    #[serde(default)]
+    pub properties: Option<RoomProperties>,
+    #[serde(default)]
+    pub room_type: Option<RoomType>,
+    #[serde(default)]
+    pub coordinates: Vec<Coordinates>,
+}
+
+/// Specification of a connection with the id `id` between the two rooms in `rooms`.
+/// <br/>`length` is the optional [length](crate::room::Building::set_connection_length) of the connection in m.
+/// <br/>`schedule` is the optional [schedule](DoorSchedule) of a door, e.g. a door that is locked from 20:00 to 06:00.
+#[derive(Deserialize, Debug)]
+pub struct ConnectionSpecification {
+    pub kind: ConnectionKind,
+    pub rooms: [String; 2],
+    pub id: i32,
+    #[serde(default)]
+    pub length: Option<f64>,
+    #[serde(default)]
+    pub schedule: Option<DoorSchedule>,
+}
+
+/// Specification of a connection between the staircase or elevator with the id `id` on two adjacent floors.
+/// <br/>`travel_time` is the time in seconds it takes to get from one floor to the other.
+#[derive(Deserialize, Debug)]
+pub struct FloorConnectionSpecification {
+    pub kind: FloorConnectionKind,
+    pub id: i32,
+    pub floors: [i32; 2],
+    pub travel_time: i64,
+}
+
+/// Kind of a connection between floors, either [stairs](crate::room::Building::connect_staircases)
+/// or an [elevator shaft](crate::room::Building::connect_elevators).
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FloorConnectionKind {
+    Staircase,
+    Elevator,
+}
+
+/// Kind of a connection, either a [door connection](crate::room::Building::new_door_connection)
+/// or a [connection without a door](crate::room::Building::new_no_door_connection).
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionKind {
+    Door,
+    NoDoor,
+}
+
+/// Specification of a room individuals move to, with its attractiveness (see [Building::set_destinations]).
+#[derive(Deserialize, Debug)]
+pub struct DestinationSpecification {
+    pub room: String,
+    #[serde(default = "default_attractiveness")]
+    pub attractiveness: u32,
+}
+
+/// Specification of how many sensors of a specific sensor type should be created in a room.
+/// <br/>The sensor type is referenced by its id, e.g. `SensorType_0`.
+#[derive(Deserialize, Debug)]
+pub struct SensorSpecification {
+    pub sensor_type: String,
+    pub count: u32,
+}
+
+fn default_doors() -> bool {
+    true
+}
+
+fn default_attractiveness() -> u32 {
+    1
+}
+
+/// Errors that can occur while loading a building from a specification file.
+#[derive(Debug)]
+pub enum SpecificationError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Toml(toml::de::Error),
+    /// A sensor type id in the file is not one of the given sensor types.
+    UnknownSensorType(String),
+    /// The building could not be created as specified, e.g. because a room id is unknown.
+    Building(BuildingError),
+}
+
+impl Display for SpecificationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpecificationError::Io(error) => write!(f, "could not read specification file: {}", error),
+            SpecificationError::Json(error) => write!(f, "could not parse JSON specification: {}", error),
+            SpecificationError::Toml(error) => write!(f, "could not parse TOML specification: {}", error),
+            SpecificationError::UnknownSensorType(id) => write!(f, "unknown sensor type: {}", id),
+            SpecificationError::Building(error) => write!(f, "invalid building specification: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for SpecificationError {}
+
+impl From<std::io::Error> for SpecificationError {
+    fn from(error: std::io::Error) -> Self {
+        SpecificationError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for SpecificationError {
+    fn from(error: serde_json::Error) -> Self {
+        SpecificationError::Json(error)
+    }
+}
+
+impl From<BuildingError> for SpecificationError {
+    fn from(error: BuildingError) -> Self {
+        SpecificationError::Building(error)
+    }
+}
+
+impl From<toml::de::Error> for SpecificationError {
+    fn from(error: toml::de::Error) -> Self {
+        SpecificationError::Toml(error)
+    }
+}
+
+impl BuildingSpecification {
+    /// Reads a building specification from a file.
+    /// <br/>Files ending with `.toml` are read as TOML, all other files as JSON.
+    pub fn from_file(path: String) -> Result<BuildingSpecification, SpecificationError> {
+        let data = fs::read_to_string(&path)?;
+        if path.ends_with(".toml") {
+            return Ok(toml::from_str(data.as_str())?);
+        }
+        return Ok(serde_json::from_str(data.as_str())?);
+    }
+}
+
+/// Converts the sensor specifications of a room into the pairs of number and [SensorType]
+/// that are used by the methods of [Building].
+fn resolve_sensors(sensors: &[SensorSpecification], sensor_types: &[SensorType]) -> Result<Vec<(u32, SensorType)>, SpecificationError> {
+    let mut res = Vec::<(u32, SensorType)>::new();
+    for sensor in sensors {
+        let sensor_type = sensor_types.iter().find(|sensor_type| sensor_type.get_id() == sensor.sensor_type);
+        match sensor_type {
+            None => return Err(SpecificationError::UnknownSensorType(sensor.sensor_type.clone())),
+            Some(sensor_type) => res.push((sensor.count, sensor_type.clone())),
+        }
+    }
+    return Ok(res);
+}
+
+impl Building {
+    /// Creates a building from a specification file (see [BuildingSpecification]).
+    /// <br/>The sensor types in the file are referenced by their id and have to be contained in `sensor_types`,
+    /// e.g. the sensor types of [`Parameters`](rubalosim::simulator::parameters::Parameters).
+    /// <br/>Returns the building and the offspring number of the last created sensor.
+    pub fn from_spec_file(path: String, sensor_types: &[SensorType], offspring_number_for_sensors: i64) -> Result<(Building, i64), SpecificationError> {
+        let specification = BuildingSpecification::from_file(path)?;
+        return Building::from_specification(&specification, sensor_types, offspring_number_for_sensors);
+    }
+
+    /// Creates a building from a [BuildingSpecification].
+    /// <br/>Returns the building and the offspring number of the last created sensor.
+    pub fn from_specification(specification: &BuildingSpecification, sensor_types: &[SensorType], offspring_number_for_sensors: i64) -> Result<(Building, i64), SpecificationError> {
+        let mut building = Building::new();
+        let mut offspring_number = offspring_number_for_sensors;
+
+        for room in &specification.rooms {
+            if !building.get_levels().iter().any(|floor| floor.get_level() == room.get_floor()) {
+                building.add_floor(room.get_floor())?;
+            }
+            building.set_current_floor(room.get_floor())?;
+            match room {
+                RoomSpecification::Staircase { id, .. } => {
+                    building.add_staircase(*id)?;
+                },
+                RoomSpecification::Elevator { id, .. } => {
+                    building.add_elevator(*id)?;
+                },
+                RoomSpecification::RoomWithDoors { id, windows, sensors, .. } => {
+                    let sensors = resolve_sensors(sensors, sensor_types)?;
+                    offspring_number = building.add_room_with_doors(*id, sensors, *windows, offspring_number)?;
+                },
+                RoomSpecification::RoomWithoutDoors { id, windows, sensors, .. } => {
+                    let sensors = resolve_sensors(sensors, sensor_types)?;
+                    offspring_number = building.add_room_without_doors(*id, sensors, *windows, offspring_number)?;
+                },
+            }
+            let number_of_rooms = building.get_number_of_rooms();
+            let id = building.get_floors()[NodeIndex::new(number_of_rooms - 1)].get_data().get_id();
+            if let Some(properties) = room.get_properties() {
+                building.set_room_properties(id.as_str(), properties.clone())?;
+            }
+            if let Some(room_type) = room.get_room_type() {
+                building.set_room_type(id.as_str(), room_type)?;
+            }
+            if let Some(coordinates) = room.get_coordinates() {
+                building.set_coordinates(id.as_str(), coordinates)?;
+            }
+        }
+
+        // connections, whose rooms do not exist yet
+        let mut pending_connections: Vec<&ConnectionSpecification> = specification.connections.iter().collect();
+        add_ready_connections(&mut building, &mut pending_connections)?;
+
+        for sub_rooms in &specification.sub_rooms {
+            let sensors = resolve_sensors(&sub_rooms.sensors, sensor_types)?;
+            let first_new_room = building.get_number_of_rooms();
+            offspring_number = if sub_rooms.doors {
+                building.add_sub_rooms_with_doors(sub_rooms.number, sub_rooms.parent.clone(), sensors, sub_rooms.windows, offspring_number)?
+            } else {
+                building.add_sub_rooms_without_doors(sub_rooms.number, sub_rooms.parent.clone(), sensors, sub_rooms.windows, offspring_number)?
+            };
+            for index in first_new_room..building.get_number_of_rooms() {
+                let id = building.get_floors()[NodeIndex::new(index)].get_data().get_id();
+                if let Some(properties) = &sub_rooms.properties {
+                    building.set_room_properties(id.as_str(), properties.clone())?;
+                }
+                if let Some(room_type) = sub_rooms.room_type {
+                    building.set_room_type(id.as_str(), room_type)?;
+                }
+                if let Some(coordinates) = sub_rooms.coordinates.get(index - first_new_room) {
+                    building.set_coordinates(id.as_str(), *coordinates)?;
+                }
+            }
+            add_ready_connections(&mut building, &mut pending_connections)?;
+        }
+        // fails, because one of the rooms does not exist
+        for connection in pending_connections {
+            add_connection(&mut building, connection)?;
+        }
+
+        for floor_connection in &specification.floor_connections {
+            let [level_1, level_2] = floor_connection.floors;
+            let travel_time = Duration::seconds(floor_connection.travel_time);
+            match floor_connection.kind {
+                FloorConnectionKind::Staircase => building.connect_staircases(floor_connection.id, level_1, level_2, travel_time)?,
+                FloorConnectionKind::Elevator => building.connect_elevators(floor_connection.id, level_1, level_2, travel_time)?,
+            };
+        }
+        building.set_current_floor(0)?;
+
+        if !specification.entrances.is_empty() {
+            building.set_entrances(specification.entrances.clone())?;
+        }
+        if !specification.exits.is_empty() {
+            building.set_exits(specification.exits.clone())?;
+        }
+        if !specification.destinations.is_empty() {
+            let destinations = specification.destinations.iter().map(|destination| (destination.room.clone(), destination.attractiveness)).collect();
+            building.set_destinations(destinations)?;
+        }
+        return Ok((building, offspring_number));
+    }
+}
+
+/// Adds the connections, whose rooms both exist, in their order and removes them from `pending_connections`.
+fn add_ready_connections(building: &mut Building, pending_connections: &mut Vec<&ConnectionSpecification>) -> Result<(), SpecificationError> {
+    let mut remaining_connections = Vec::<&ConnectionSpecification>::new();
+    for connection in pending_connections.drain(..) {
+        if connection.rooms.iter().all(|id| building.node_index(id.as_str()).is_some()) {
+            add_connection(building, connection)?;
+        } else {
+            remaining_connections.push(connection);
+        }
+    }
+    *pending_connections = remaining_connections;
+    return Ok(());
+}
+
+/// Adds a connection with its length and schedule to the building.
+fn add_connection(building: &mut Building, connection: &ConnectionSpecification) -> Result<(), SpecificationError> {
+    let [id_room_1, id_room_2] = connection.rooms.clone();
+    match connection.kind {
+        ConnectionKind::Door => building.new_door_connection(id_room_1.clone(), id_room_2.clone(), connection.id)?,
+        ConnectionKind::NoDoor => building.new_no_door_connection(id_room_1.clone(), id_room_2.clone(), connection.id)?,
+    };
+    if let Some(length) = connection.length {
+        building.set_connection_length(id_room_1.as_str(), id_room_2.as_str(), length)?;
+    }
+    if let Some(schedule) = &connection.schedule {
+        building.set_door_schedule(id_room_1.as_str(), id_room_2.as_str(), schedule.clone())?;
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::visit::EdgeRef;
+    use crate::builder::BuildingBuilder;
+    use crate::testing::sensor_types;
+    use super::*;
+
+    /// Gets the id and the sensor ids and numbers of every node and the rooms and id of every edge, in the order of the graph.
+    fn describe(building: &Building) -> (Vec<(String, Vec<(String, i64)>)>, Vec<(String, String, String)>) {
+        let graph = building.get_floors();
+        let nodes = graph.node_indices()
+            .map(|index| (graph[index].get_data().get_id(), graph[index].get_sensors().iter().map(|sensor| (sensor.get_id(), sensor.get_number())).collect()))
+            .collect();
+        let edges = graph.edge_references()
+            .map(|edge| (graph[edge.source()].get_data().get_id(), graph[edge.target()].get_data().get_id(), edge.weight().get_data().get_id()))
+            .collect();
+        return (nodes, edges);
+    }
+
+    #[test]
+    fn specification_file_gives_the_graph_of_main() {
+        let sensor_types = sensor_types();
+        let (building, offspring_number) = Building::from_spec_file("specification_files/building_specification.json".to_string(), &sensor_types, -1).unwrap();
+
+        // the structure of create_building in main.rs
+        let sensor_types_for_rooms = vec![(1, sensor_types[0].clone()), (2, sensor_types[1].clone())];
+        let mut builder = BuildingBuilder::new();
+        for i in 0..12 {
+            if i <= 1 {
+                builder.add_room_with_doors(i, sensor_types_for_rooms.clone(), false).unwrap();
+            }
+            if i <= 4 {
+                builder.add_staircase(i).unwrap();
+            }
+            builder.add_room_without_doors(i, sensor_types_for_rooms.clone(), false).unwrap();
+        }
+        let connections = [("RwnD0", "RwD0"), ("RwD0", "RwnD1"), ("RwnD1", "S0"), ("RwnD1", "RwD1"), ("RwnD1","RwnD2"), ("RwnD2", "RwnD3"), ("RwnD2", "RwnD4"), ("RwnD4", "S1"), ("RwnD4", "RwnD5"), ("RwnD5", "RwnD0"), ("RwnD5", "S2"), ("RwnD5", "RwnD6"), ("RwnD6", "RwnD7"), ("RwnD7", "S3"), ("RwnD7", "RwnD8"), ("RwnD8", "RwnD4"), ("RwnD8", "RwnD9"), ("RwnD9", "RwnD10"), ("RwnD10", "RwnD7"), ("RwnD10", "S4"), ("RwnD10", "RwnD11"), ("RwnD11", "RwD1")];
+        for (i, (id_room_1, id_room_2)) in connections.into_iter().enumerate() {
+            if id_room_1.contains("RwD") | id_room_2.contains("RwD") {
+                builder.new_door_connection(id_room_1.to_string(), id_room_2.to_string(), i as i32).unwrap();
+            } else {
+                builder.new_no_door_connection(id_room_1.to_string(), id_room_2.to_string(), i as i32).unwrap();
+            }
+        }
+        builder.add_sub_rooms_with_doors(14, "RwnD0".to_string(), sensor_types_for_rooms.clone(), true).unwrap();
+        builder.new_door_connection("RwnD0_RwD10_sub".to_string(), "RwnD0_RwD11_sub".to_string(), 1).unwrap();
+        for (number, parent_id) in [(7, "RwnD1"), (7, "RwnD5"), (3, "RwnD6"), (6, "RwnD7"), (3, "RwnD8"), (5, "RwnD9"), (10, "RwnD10"), (9, "RwnD11"), (5, "RwD0")] {
+            builder.add_sub_rooms_with_doors(number, parent_id.to_string(), sensor_types_for_rooms.clone(), true).unwrap();
+        }
+        builder.new_door_connection("RwD0_RwD0_sub".to_string(), "RwD0_RwD1_sub".to_string(), 0).unwrap();
+        builder.new_door_connection("RwD0_RwD2_sub".to_string(), "RwD0_RwD3_sub".to_string(), 1).unwrap();
+        builder.add_sub_rooms_with_doors(3, "RwD1".to_string(), sensor_types_for_rooms.clone(), true).unwrap();
+        let (expected_building, expected_offspring_number) = builder.build();
+
+        assert_eq!(describe(&building), describe(&expected_building));
+        assert_eq!(offspring_number, expected_offspring_number);
+    }
+}
@@ -0,0 +1,16 @@
+use std::sync::Arc;
+use chrono::{Duration, NaiveTime};
+use rubalosim::sensor::SensorType;
+use rubalosim::simulator::parameters::Parameters;
+use crate::human::Individual;
+
+/// Gets the sensor types of the device profiles of _main.rs_, in the same order:
+/// light, occupancy, door contact and people counter.
+pub(crate) fn sensor_types() -> Vec<SensorType> {
+    let mut parameters = Parameters::new(Arc::new(Individual::new(0, NaiveTime::MIN, NaiveTime::MIN, 0, Duration::zero())));
+    parameters.add_device_profile_via_file(850, Some("specification_files/downlink_specification_file_2_light.json"), Some("specification_files/uplink_specification_file_2_light.json")).unwrap();
+    parameters.add_device_profile_via_file(250, None, Some("specification_files/uplink_specification_file_3.json")).unwrap();
+    parameters.add_device_profile_via_file(250, None, Some("specification_files/uplink_specification_file_4_contact.json")).unwrap();
+    parameters.add_device_profile_via_file(250, None, Some("specification_files/uplink_specification_file_5_counter.json")).unwrap();
+    return parameters.get_sensor_types();
+}